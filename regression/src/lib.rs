@@ -0,0 +1,75 @@
+//! Compile-time regression tests of type-vec on very large static lengths.
+//!
+//! The functions here are only type-checked. Each one runs a chain of 42 operations
+//! on a vector of a large static length, with the default `recursion_limit`. If the
+//! cost of the size machinery grows with the length, this crate stops compiling or
+//! slows down noticeably. See the README for the measurements.
+
+use type_vec::{size, Vect};
+use typenum::consts::*;
+
+/// Pushes and inserts 20 elements each, then pops and removes one each.
+macro_rules! op_chain {
+    ($vec:expr) => {{
+        let vec = $vec
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2)
+            .push(1)
+            .insert(U1::new(), 2);
+        let (vec, _) = vec.pop();
+        let (vec, _) = vec.remove(U0::new());
+        vec
+    }};
+}
+
+pub fn inline_u4096(vec: Vect<u8, U4096>) -> Vect<u8, size!(U4096 + U38)> {
+    op_chain!(vec)
+}
+
+pub fn inline_u65536(vec: Vect<u8, U65536>) -> Vect<u8, size!(U65536 + U38)> {
+    op_chain!(vec)
+}
+
+pub fn heap_u65536(vec: Vect<u8, U65536, Vec<u8>>) -> Vect<u8, size!(U65536 + U38), Vec<u8>> {
+    op_chain!(vec)
+}
+
+pub fn heap_u1048576(vec: Vect<u8, U1048576, Vec<u8>>) -> Vect<u8, size!(U1048576 + U38), Vec<u8>> {
+    op_chain!(vec)
+}
@@ -0,0 +1,73 @@
+//! Regression tests that type-check vector operations on very large static sizes.
+
+use type_vec::{size, Dyn, Vect};
+use typenum::consts::*;
+
+#[test]
+fn large_size_ops_test() {
+    let vec = Vect::<u8, Dyn>::from_vec(vec![0; 4096])
+        .into_static::<U4096>()
+        .unwrap();
+
+    let vec: Vect<u8, size!(U4096 + 1)> = vec.push(1);
+    let vec = vec.push(2).insert(5, 4).insert(U1024::new(), 3);
+    let (vec, elem) = vec.pop();
+    assert_eq!(elem, 2);
+    let (vec, elem) = vec.remove(U1024::new());
+    assert_eq!(elem, 3);
+    let (vec, _) = vec.remove(7);
+    let vec: Vect<u8, size!(U4096 + 1)> = vec;
+
+    assert_eq!(vec.get(U4096::new()), &1);
+    assert_eq!(vec.get(4096), Some(&1));
+    assert_eq!(vec.get(4097), None);
+    assert_eq!(vec.len(), 4097);
+}
+
+#[test]
+fn huge_size_ops_test() {
+    // huge vectors are kept on the heap rather than on the stack
+    let vec = Vect::<u8, U65536, Vec<u8>>::try_from_vec(vec![0; 65536]).unwrap();
+
+    let vec = vec
+        .push(1)
+        .push(2)
+        .push(3)
+        .insert(U0::new(), 4)
+        .insert(U65536::new(), 5)
+        .insert(U1024::new(), 6);
+    let (vec, elem) = vec.pop();
+    assert_eq!(elem, 3);
+    let (vec, elem) = vec.remove(U1024::new());
+    assert_eq!(elem, 6);
+    assert_eq!(vec.get(U65536::new()), &5);
+    assert_eq!(vec.get(U0::new()), &4);
+    assert_eq!(vec.len(), 65536 + 4);
+
+    let vec = vec.into_vec();
+    assert!(Vect::<u8, U1048576, Vec<u8>>::try_from_vec(vec).is_err());
+}
+
+#[test]
+fn long_op_chain_test() {
+    let vec = Vect::<u8, U1048576, Vec<u8>>::try_from_vec(vec![0; 1 << 20]).unwrap();
+    let vec = vec
+        .push(1)
+        .push(1)
+        .push(1)
+        .push(1)
+        .push(1)
+        .push(1)
+        .push(1)
+        .push(1)
+        .insert(U1::new(), 2)
+        .insert(U1::new(), 2)
+        .insert(U1::new(), 2)
+        .insert(U1::new(), 2)
+        .insert(U1::new(), 2)
+        .insert(U1::new(), 2)
+        .insert(U1::new(), 2)
+        .insert(U1::new(), 2);
+    assert_eq!(vec.len(), (1 << 20) + 16);
+    assert_eq!(vec.get(U8::new()), &2);
+}
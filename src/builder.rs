@@ -0,0 +1,171 @@
+//! The builder that initializes a vector with static length element by element.
+//!
+//! The number of remaining elements is tracked at type level, so that
+//! [finish](VectBuilder::finish) only compiles after every element is set.
+//!
+//! ```rust
+//! use type_vec::{Vect, VectBuilder};
+//! use typenum::consts::*;
+//!
+//! fn header(builder: VectBuilder<u8, U4>) -> VectBuilder<u8, U4, U2> {
+//!     builder.push(0xCA).push(0xFE)
+//! }
+//!
+//! let builder = header(VectBuilder::new()).push(0xBA);
+//! let vec: Vect<u8, U4> = builder.push(0xBE).finish();
+//! assert_eq!(vec, [0xCA, 0xFE, 0xBA, 0xBE]);
+//! ```
+//!
+//! Finishing the builder with a missing element does not compile.
+//!
+//! ```compile_fail,E0599
+//! use type_vec::VectBuilder;
+//! use typenum::consts::*;
+//!
+//! let builder = VectBuilder::<u8, U4>::new().push(0xCA).push(0xFE).push(0xBA);
+//! let vec = builder.finish();
+//! ```
+
+use crate::{
+    common::*,
+    size::{self, Size},
+    storage::{Array, InlineLen},
+    vect::Vect,
+};
+
+/// The builder of a vector of length `N`, which has `R` elements left to set.
+///
+/// The elements are written in place into an uninitialized inline [Array].
+/// If the builder is dropped before it is finished, the elements set so far are dropped.
+pub struct VectBuilder<T, N, R = N>
+where
+    N: InlineLen,
+    R: Unsigned,
+{
+    data: mem::MaybeUninit<Array<T, N>>,
+    _phantom: PhantomData<R>,
+}
+
+impl<T, N> VectBuilder<T, N>
+where
+    N: InlineLen,
+{
+    /// Creates a builder with no elements set.
+    pub fn new() -> Self {
+        Self {
+            data: mem::MaybeUninit::uninit(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, N> Default for VectBuilder<T, N>
+where
+    N: InlineLen,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, N, R> VectBuilder<T, N, R>
+where
+    N: InlineLen,
+    R: Unsigned,
+{
+    /// Gets the number of elements set so far.
+    pub fn len(&self) -> usize {
+        N::USIZE - R::USIZE
+    }
+
+    /// Returns true if no element is set.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the number of elements left to set.
+    pub fn remaining(&self) -> usize {
+        R::USIZE
+    }
+
+    /// Returns the elements set so far as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        if self.is_empty() {
+            return &[];
+        }
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { slice::from_raw_parts(self.data.as_ptr() as *const T, self.len()) }
+    }
+
+    /// Sets the next element.
+    ///
+    /// It does not compile if every element is already set.
+    ///
+    /// ```compile_fail,E0599
+    /// use type_vec::VectBuilder;
+    /// use typenum::consts::*;
+    ///
+    /// let builder = VectBuilder::<usize, U1>::new().push(1).push(2);
+    /// ```
+    pub fn push(self, elem: T) -> VectBuilder<T, N, size::DecreaseOneOp<R>>
+    where
+        R: Size,
+        (): size::DecreaseOne<R>,
+        size::DecreaseOneOp<R>: Unsigned,
+    {
+        let len = self.len();
+        let mut this = mem::ManuallyDrop::new(self);
+        // SAFETY: the slot at `len` is within the array and uninitialized, since `R > 0`.
+        unsafe { (this.data.as_mut_ptr() as *mut T).add(len).write(elem) };
+        VectBuilder {
+            // SAFETY: `this` is not dropped, so the data is moved out once.
+            data: unsafe { ptr::read(&this.data) },
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, N> VectBuilder<T, N, U0>
+where
+    N: InlineLen + Size,
+{
+    /// Builds the vector after every element is set.
+    pub fn finish(self) -> Vect<T, N, Array<T, N>> {
+        let this = mem::ManuallyDrop::new(self);
+        // SAFETY: all N elements are initialized, and `this` is not dropped.
+        let data = unsafe { ptr::read(&this.data).assume_init() };
+        Vect {
+            data,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, N, R> Drop for VectBuilder<T, N, R>
+where
+    N: InlineLen,
+    R: Unsigned,
+{
+    fn drop(&mut self) {
+        if self.is_empty() {
+            return;
+        }
+        let ptr = self.data.as_mut_ptr() as *mut T;
+        // SAFETY: the first `len` elements are initialized and dropped once.
+        unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr, self.len())) }
+    }
+}
+
+impl<T, N, R> Debug for VectBuilder<T, N, R>
+where
+    T: Debug,
+    N: InlineLen,
+    R: Unsigned,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VectBuilder")
+            .field("elements", &self.as_slice())
+            .field("remaining", &self.remaining())
+            .finish()
+    }
+}
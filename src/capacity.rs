@@ -0,0 +1,323 @@
+//! Vectors with type-level capacity.
+//!
+//! A [CapVect] allocates its capacity once on creation. Length-increasing
+//! operations only compile while the length is less than the capacity, so that a
+//! sequence of typed operations provably never reallocates.
+//!
+//! ```rust
+//! use type_vec::CapVect;
+//! use typenum::consts::*;
+//!
+//! let vec = CapVect::<usize, U0, U2>::new();
+//! let vec = vec.push(1).push(2);
+//!
+//! let (vec, elem) = vec.pop();
+//! assert_eq!(elem, 2);
+//! let vec = vec.push(3);
+//!
+//! // erase the capacity
+//! let vec = vec.into_vect();
+//! assert_eq!(vec, [1, 3]);
+//! ```
+//!
+//! Pushing to a full vector does not compile.
+//!
+//! ```compile_fail,E0277
+//! use type_vec::CapVect;
+//! use typenum::consts::*;
+//!
+//! let vec = CapVect::<usize, U0, U2>::new().push(1).push(2);
+//! let vec = vec.push(3);
+//! ```
+
+use crate::{
+    common::*,
+    predicate::{AssertSizeLe, AssertSizeLt},
+    size::{self, Size},
+    storage::Storage,
+    vect::Vect,
+};
+
+/// The vector with type-level length `S` and type-level capacity `Cap`.
+///
+/// It keeps the elements in a [Vec] that holds at least `Cap` elements without
+/// reallocating. It dereferences to the underlying [Vect] for read accesses,
+/// and gives mutable access to the elements only by indexing and
+/// [as_mut_slice](CapVect::as_mut_slice).
+pub struct CapVect<T, S, Cap>
+where
+    S: Size,
+    Cap: Unsigned + Size,
+{
+    vect: Vect<T, S, Vec<T>>,
+    _phantom: PhantomData<Cap>,
+}
+
+impl<T, Cap> CapVect<T, U0, Cap>
+where
+    Cap: Unsigned + Size,
+{
+    /// Creates an empty vector and allocates the capacity.
+    pub fn new() -> Self {
+        Self {
+            vect: Vect::<T, U0, Vec<T>>::with_capacity(Cap::USIZE),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Cap> Default for CapVect<T, U0, Cap>
+where
+    Cap: Unsigned + Size,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, S, Cap> CapVect<T, S, Cap>
+where
+    S: Unsigned + Size,
+    Cap: Unsigned + Size,
+{
+    /// Converts from a vector with static length, and reserves the capacity.
+    ///
+    /// It does not compile if the length exceeds the capacity.
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::{CapVect, Vect};
+    /// use typenum::consts::*;
+    ///
+    /// let vec = CapVect::<usize, U3, U2>::from_vect(Vect::from([1, 2, 3]));
+    /// ```
+    pub fn from_vect<D>(vect: Vect<T, S, D>) -> Self
+    where
+        D: Storage<T>,
+        (): AssertSizeLe<S, Cap>,
+    {
+        let mut data = vect.into_vec();
+        data.reserve_exact(Cap::USIZE - S::USIZE);
+        Self {
+            vect: Vect {
+                data,
+                _phantom: PhantomData,
+            },
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Appends an element to the end of the vector without reallocation.
+    ///
+    /// It does not compile if the vector is full.
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::CapVect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = CapVect::<usize, U0, U2>::new().push(1).push(2);
+    /// let vec = vec.push(3);
+    /// ```
+    pub fn push(self, elem: T) -> CapVect<T, size::IncreaseOneOp<S>, Cap>
+    where
+        (): size::IncreaseOne<S> + AssertSizeLt<S, Cap>,
+    {
+        CapVect {
+            vect: self.vect.push(elem),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Inserts an element at a static index without reallocation.
+    ///
+    /// It does not compile if the vector is full or the index is out of bound.
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::CapVect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = CapVect::<usize, U0, U2>::new().push(1).push(2);
+    /// let vec = vec.insert(U0::new(), 0);
+    /// ```
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::CapVect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = CapVect::<usize, U0, U4>::new().push(1);
+    /// let vec = vec.insert(U2::new(), 2);
+    /// ```
+    pub fn insert<I>(self, index: I, elem: T) -> CapVect<T, size::IncreaseOneOp<S>, Cap>
+    where
+        I: Unsigned + Size,
+        (): size::IncreaseOne<S> + size::CheckIndexInclusive<S, I> + AssertSizeLt<S, Cap>,
+    {
+        let data =
+            Storage::insert::<size::IncreaseOneOp<S>>(self.vect.data, index.to_usize(), elem);
+        CapVect {
+            vect: Vect {
+                data,
+                _phantom: PhantomData,
+            },
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Removes an element from the end of the vector.
+    ///
+    /// It does not compile if the vector is empty.
+    ///
+    /// ```compile_fail,E0599
+    /// use type_vec::CapVect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = CapVect::<usize, U0, U4>::new();
+    /// let (vec, elem) = vec.pop();
+    /// ```
+    pub fn pop(self) -> (CapVect<T, size::DecreaseOneOp<S>, Cap>, T)
+    where
+        (): size::DecreaseOne<S>,
+    {
+        let (data, elem) = match Storage::pop::<size::DecreaseOneOp<S>>(self.vect.data) {
+            Ok(output) => output,
+            Err(_) => unreachable!("the static length is positive"),
+        };
+        let vect = Vect {
+            data,
+            _phantom: PhantomData,
+        };
+        (
+            CapVect {
+                vect,
+                _phantom: PhantomData,
+            },
+            elem,
+        )
+    }
+
+    /// Removes an element at a static index.
+    ///
+    /// It does not compile if the index is out of bound.
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::CapVect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = CapVect::<usize, U0, U4>::new().push(1);
+    /// let (vec, elem) = vec.remove(U1::new());
+    /// ```
+    pub fn remove<I>(self, index: I) -> (CapVect<T, size::DecreaseOneOp<S>, Cap>, T)
+    where
+        I: Unsigned + Size,
+        (): size::DecreaseOne<S> + size::CheckIndex<S, I>,
+    {
+        let (data, elem) =
+            Storage::remove::<size::DecreaseOneOp<S>>(self.vect.data, index.to_usize());
+        let vect = Vect {
+            data,
+            _phantom: PhantomData,
+        };
+        (
+            CapVect {
+                vect,
+                _phantom: PhantomData,
+            },
+            elem,
+        )
+    }
+}
+
+impl<T, S, Cap> CapVect<T, S, Cap>
+where
+    S: Size,
+    Cap: Unsigned + Size,
+{
+    /// Gets the guaranteed capacity `Cap`.
+    pub fn capacity(&self) -> usize {
+        Cap::USIZE
+    }
+
+    /// Gets a reference to the underlying vector.
+    pub fn as_vect(&self) -> &Vect<T, S, Vec<T>> {
+        &self.vect
+    }
+
+    /// Returns the elements as a mutable slice.
+    ///
+    /// The vector itself is not handed out mutably, so that its buffer cannot be
+    /// replaced by one with less capacity.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.vect.data
+    }
+
+    /// Erases the capacity and returns the underlying vector.
+    pub fn into_vect(self) -> Vect<T, S, Vec<T>> {
+        self.vect
+    }
+}
+
+impl<T, S, Cap> Deref for CapVect<T, S, Cap>
+where
+    S: Size,
+    Cap: Unsigned + Size,
+{
+    type Target = Vect<T, S, Vec<T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vect
+    }
+}
+
+impl<T, S, Cap, I> Index<I> for CapVect<T, S, Cap>
+where
+    S: Size,
+    Cap: Unsigned + Size,
+    Vect<T, S, Vec<T>>: Index<I>,
+{
+    type Output = <Vect<T, S, Vec<T>> as Index<I>>::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &self.vect[index]
+    }
+}
+
+impl<T, S, Cap, I> IndexMut<I> for CapVect<T, S, Cap>
+where
+    S: Size,
+    Cap: Unsigned + Size,
+    Vect<T, S, Vec<T>>: IndexMut<I>,
+{
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.vect[index]
+    }
+}
+
+impl<T, S, Cap> Debug for CapVect<T, S, Cap>
+where
+    T: Debug,
+    S: Size,
+    Cap: Unsigned + Size,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.vect, f)
+    }
+}
+
+/// The clone allocates the capacity `Cap` as well.
+impl<T, S, Cap> Clone for CapVect<T, S, Cap>
+where
+    T: Clone,
+    S: Size,
+    Cap: Unsigned + Size,
+{
+    fn clone(&self) -> Self {
+        let mut data = Vec::with_capacity(Cap::USIZE);
+        data.extend_from_slice(&self.vect.data);
+        Self {
+            vect: Vect {
+                data,
+                _phantom: PhantomData,
+            },
+            _phantom: PhantomData,
+        }
+    }
+}
@@ -1,3 +1,27 @@
-pub use std::marker::PhantomData;
+pub use core::{
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    iter::{self, FromIterator, FusedIterator},
+    marker::PhantomData,
+    mem,
+    ops::{Add, Deref, DerefMut, Index, IndexMut, Mul, Sub},
+    ptr, slice,
+};
 pub use typ::typ;
-pub use typenum::{consts::*, Bit, UInt, UTerm, Unsigned};
+pub use typenum::{consts::*, Bit, Diff, UInt, UTerm, Unsigned, B0, B1};
+
+#[cfg(feature = "alloc")]
+pub use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::{vec_deque, VecDeque},
+    rc::Rc,
+    vec,
+    vec::Vec,
+};
+
+#[cfg(feature = "std")]
+pub use std::error::Error as StdError;
@@ -0,0 +1,146 @@
+//! Conversions between [Vect] and standard types.
+
+use crate::{common::*, error::LengthError, size::Size, storage::Storage, vect::Vect};
+#[cfg(feature = "alloc")]
+use crate::{error::DynLengthError, size::Dyn};
+use typenum::{Const, ToUInt, U};
+
+// from standard types
+
+#[cfg(feature = "alloc")]
+impl<T> From<Vec<T>> for Vect<T, Dyn> {
+    fn from(data: Vec<T>) -> Self {
+        Self::from_vec(data)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> From<Box<[T]>> for Vect<T, Dyn> {
+    fn from(data: Box<[T]>) -> Self {
+        Self::from_vec(data.into_vec())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> From<Cow<'a, [T]>> for Vect<T, Dyn>
+where
+    T: Clone,
+{
+    fn from(data: Cow<'a, [T]>) -> Self {
+        Self::from_vec(data.into_owned())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vect<T, U<N>>
+where
+    Const<N>: ToUInt,
+    U<N>: Size,
+{
+    fn from(data: [T; N]) -> Self {
+        Vect {
+            data: Storage::from_exact_iter(data),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S> TryFrom<Vec<T>> for Vect<T, S>
+where
+    S: Unsigned + Size,
+{
+    type Error = DynLengthError<T>;
+
+    fn try_from(data: Vec<T>) -> Result<Self, Self::Error> {
+        Vect::from_vec(data).into_static()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S> TryFrom<Box<[T]>> for Vect<T, S>
+where
+    S: Unsigned + Size,
+{
+    type Error = LengthError<Box<[T]>>;
+
+    fn try_from(data: Box<[T]>) -> Result<Self, Self::Error> {
+        if data.len() == S::USIZE {
+            Ok(Vect {
+                data: Storage::from_vec(data.into_vec()),
+                _phantom: PhantomData,
+            })
+        } else {
+            Err(LengthError::new(S::USIZE, data.len(), data))
+        }
+    }
+}
+
+impl<'a, T, S> TryFrom<&'a [T]> for Vect<T, S>
+where
+    T: Clone,
+    S: Unsigned + Size,
+{
+    type Error = LengthError<&'a [T]>;
+
+    fn try_from(data: &'a [T]) -> Result<Self, Self::Error> {
+        if data.len() == S::USIZE {
+            Ok(Vect {
+                data: Storage::from_exact_iter(data.iter().cloned()),
+                _phantom: PhantomData,
+            })
+        } else {
+            Err(LengthError::new(S::USIZE, data.len(), data))
+        }
+    }
+}
+
+// into standard types
+
+#[cfg(feature = "alloc")]
+impl<T, S, D> From<Vect<T, S, D>> for Vec<T>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    fn from(vec: Vect<T, S, D>) -> Self {
+        vec.into_vec()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S, D> From<Vect<T, S, D>> for Box<[T]>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    fn from(vec: Vect<T, S, D>) -> Self {
+        vec.into_vec().into_boxed_slice()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, S, D> From<Vect<T, S, D>> for Cow<'a, [T]>
+where
+    T: Clone,
+    S: Size,
+    D: Storage<T>,
+{
+    fn from(vec: Vect<T, S, D>) -> Self {
+        Cow::Owned(vec.into_vec())
+    }
+}
+
+impl<T, D, const N: usize> From<Vect<T, U<N>, D>> for [T; N]
+where
+    Const<N>: ToUInt,
+    U<N>: Size,
+    D: Storage<T>,
+{
+    fn from(vec: Vect<T, U<N>, D>) -> Self {
+        let mut iter = vec.into_iter();
+        [(); N].map(|()| match iter.next() {
+            Some(item) => item,
+            None => unreachable!("the length is guaranteed by the type"),
+        })
+    }
+}
@@ -0,0 +1,182 @@
+//! Error types.
+//!
+//! Every error returns the data it was given, so that a failed conversion or
+//! operation does not drop the vector.
+
+use crate::common::*;
+#[cfg(feature = "alloc")]
+use crate::{
+    size::{Dyn, Size},
+    storage::Storage,
+    vect::Vect,
+};
+
+/// The error when the number of elements does not match the expected length.
+///
+/// It gives the input back in `data`, so that the caller does not lose it.
+/// Owned input is usually given back as a vector with dynamic length.
+/// Conversions from borrowed input, such as slices, return the borrowed input instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LengthError<D> {
+    /// The expected length.
+    pub expected: usize,
+    /// The observed length.
+    pub actual: usize,
+    /// The input data.
+    pub data: D,
+}
+
+/// The [LengthError] that gives a vector with dynamic length back.
+#[cfg(feature = "alloc")]
+pub type DynLengthError<T> = LengthError<Vect<T, Dyn>>;
+
+impl<D> LengthError<D> {
+    pub(crate) fn new(expected: usize, actual: usize, data: D) -> Self {
+        Self {
+            expected,
+            actual,
+            data,
+        }
+    }
+
+    /// Takes the input data back.
+    pub fn into_data(self) -> D {
+        self.data
+    }
+}
+
+impl<D> Display for LengthError<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected length {}, but found {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D> StdError for LengthError<D> where D: Debug {}
+
+/// The error when an index is out of bound.
+///
+/// It gives the input back in `data`. Operations that only borrow the vector
+/// carry no data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexError<D = ()> {
+    /// The requested index.
+    pub index: usize,
+    /// The length of the vector.
+    pub len: usize,
+    /// The input data.
+    pub data: D,
+}
+
+impl<D> IndexError<D> {
+    pub(crate) fn new(index: usize, len: usize, data: D) -> Self {
+        Self { index, len, data }
+    }
+
+    /// Takes the input data back.
+    pub fn into_data(self) -> D {
+        self.data
+    }
+}
+
+impl<D> Display for IndexError<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "index {} is out of bound for length {}",
+            self.index, self.len
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D> StdError for IndexError<D> where D: Debug {}
+
+/// The error type that covers every error of the crate.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error<T> {
+    /// The length does not match.
+    Length(DynLengthError<T>),
+    /// The index is out of bound.
+    Index(IndexError<Vect<T, Dyn>>),
+    /// The insertion index is out of bound. The element is given back with the vector.
+    Insert(IndexError<(Vect<T, Dyn>, T)>),
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Error<T> {
+    /// Takes the vector back.
+    ///
+    /// The element of a failed insertion is dropped. Match on [Insert](Error::Insert)
+    /// to take it back as well.
+    pub fn into_data(self) -> Vect<T, Dyn> {
+        match self {
+            Self::Length(err) => err.into_data(),
+            Self::Index(err) => err.into_data(),
+            Self::Insert(err) => err.into_data().0,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S, D> From<LengthError<Vect<T, S, D>>> for Error<T>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    fn from(err: LengthError<Vect<T, S, D>>) -> Self {
+        let LengthError {
+            expected,
+            actual,
+            data,
+        } = err;
+        Self::Length(LengthError::new(expected, actual, data.into_dyn()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S, D> From<IndexError<Vect<T, S, D>>> for Error<T>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    fn from(err: IndexError<Vect<T, S, D>>) -> Self {
+        let IndexError { index, len, data } = err;
+        Self::Index(IndexError::new(index, len, data.into_dyn()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S, D> From<IndexError<(Vect<T, S, D>, T)>> for Error<T>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    fn from(err: IndexError<(Vect<T, S, D>, T)>) -> Self {
+        let IndexError {
+            index,
+            len,
+            data: (vec, elem),
+        } = err;
+        Self::Insert(IndexError::new(index, len, (vec.into_dyn(), elem)))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Display for Error<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(err) => Display::fmt(err, f),
+            Self::Index(err) => Display::fmt(err, f),
+            Self::Insert(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> StdError for Error<T> where T: Debug {}
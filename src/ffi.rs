@@ -0,0 +1,234 @@
+//! Passing vectors to and from C.
+//!
+//! A vector with static length in the inline [Array] storage has the same layout
+//! as `[T; N]`. It can be passed to C as a `*const [T; N]` or `*mut [T; N]` pointer.
+//! The length of the array type is checked against the vector length in compile time.
+//! It is inferred from the expected pointer type, or given as in `as_array_ptr::<4>()`.
+//!
+//! A [CVect] is a `#[repr(C)]` borrowed view of a pointer and a length, which can
+//! be passed to C by value. Views coming from C are imported into vectors with static
+//! length by checking the length in runtime.
+//!
+//! ```rust
+//! use std::convert::TryInto;
+//! use type_vec::{ffi::CVect, Vect};
+//! use typenum::consts::*;
+//!
+//! extern "C" fn sum(data: *const [u32; 3]) -> u32 {
+//!     unsafe { (*data).iter().sum() }
+//! }
+//!
+//! let vec = Vect::<u32, U3>::from([1, 2, 3]);
+//! assert_eq!(sum(vec.as_array_ptr()), 6);
+//!
+//! let view = CVect::new(&[4u32, 5, 6]);
+//! let vec: Vect<u32, U3> = view.try_into().unwrap();
+//! assert_eq!(vec, [4, 5, 6]);
+//! ```
+//!
+//! Passing a pointer to an array of a different length does not compile.
+//!
+//! ```compile_fail,E0271
+//! use type_vec::Vect;
+//! use typenum::consts::*;
+//!
+//! extern "C" fn sum(data: *const [u32; 3]) -> u32 {
+//!     unsafe { (*data).iter().sum() }
+//! }
+//!
+//! let vec = Vect::<u32, U2>::from([1, 2]);
+//! sum(vec.as_array_ptr());
+//! ```
+
+use crate::{
+    common::*,
+    error::LengthError,
+    size::Size,
+    storage::{Array, ContiguousStorage, InlineLen, Storage},
+    vect::Vect,
+};
+use typenum::{Const, ToUInt};
+
+/// The vector with static length in the inline storage, which has the layout of `[T; S]`.
+pub type InlineVect<T, S> = Vect<T, S, Array<T, S>>;
+
+impl<T, S> Vect<T, S, Array<T, S>>
+where
+    S: InlineLen + Size,
+{
+    /// Returns a pointer to the elements as an array.
+    ///
+    /// It does not compile if `N` differs from the vector length.
+    pub fn as_array_ptr<const N: usize>(&self) -> *const [T; N]
+    where
+        Const<N>: ToUInt<Output = S>,
+    {
+        self.data.as_slice().as_ptr() as *const [T; N]
+    }
+
+    /// Returns a mutable pointer to the elements as an array.
+    ///
+    /// It does not compile if `N` differs from the vector length.
+    pub fn as_mut_array_ptr<const N: usize>(&mut self) -> *mut [T; N]
+    where
+        Const<N>: ToUInt<Output = S>,
+    {
+        self.data.as_mut_slice().as_mut_ptr() as *mut [T; N]
+    }
+
+    /// Returns a reference to the elements as an array.
+    pub fn as_array<const N: usize>(&self) -> &[T; N]
+    where
+        Const<N>: ToUInt<Output = S>,
+    {
+        // SAFETY: the storage has the same layout as `[T; N]`.
+        unsafe { &*self.as_array_ptr() }
+    }
+
+    /// Returns a mutable reference to the elements as an array.
+    pub fn as_mut_array<const N: usize>(&mut self) -> &mut [T; N]
+    where
+        Const<N>: ToUInt<Output = S>,
+    {
+        // SAFETY: the storage has the same layout as `[T; N]`.
+        unsafe { &mut *self.as_mut_array_ptr() }
+    }
+}
+
+impl<T, S, D> Vect<T, S, D>
+where
+    S: Size,
+    D: ContiguousStorage<T>,
+{
+    /// Borrows the elements as a [CVect].
+    pub fn as_c_vect(&self) -> CVect<'_, T> {
+        CVect::new(self.data.as_slice())
+    }
+}
+
+/// The borrowed view of elements that can be passed to C.
+///
+/// It is laid out as a C struct with a `const T *` pointer and a `size_t` length.
+/// The pointer may be null if the length is zero.
+#[repr(C)]
+pub struct CVect<'a, T> {
+    ptr: *const T,
+    len: usize,
+    _phantom: PhantomData<&'a [T]>,
+}
+
+impl<'a, T> CVect<'a, T> {
+    /// Borrows a slice.
+    pub fn new(slice: &'a [T]) -> Self {
+        Self {
+            ptr: slice.as_ptr(),
+            len: slice.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a view from a pointer and a length.
+    ///
+    /// # Safety
+    /// If `len` is positive, `ptr` must point to `len` initialized elements that are
+    /// valid and not mutated for the lifetime `'a`, like [slice::from_raw_parts].
+    pub unsafe fn from_raw_parts(ptr: *const T, len: usize) -> Self {
+        Self {
+            ptr,
+            len,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Gets the pointer to the first element.
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Gets the number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the view contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the elements as a slice.
+    pub fn as_slice(&self) -> &'a [T] {
+        if self.len == 0 {
+            return &[];
+        }
+        // SAFETY: guaranteed by the constructors.
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    /// Reinterprets the elements as a vector with static length without copying.
+    ///
+    /// It fails if the length of the view differs from `S`.
+    pub fn try_as_vect<S>(self) -> Result<&'a InlineVect<T, S>, LengthError<Self>>
+    where
+        S: InlineLen + Size,
+    {
+        if self.len != S::USIZE {
+            return Err(LengthError::new(S::USIZE, self.len, self));
+        }
+        let ptr = self.as_slice().as_ptr() as *const InlineVect<T, S>;
+        // SAFETY: the vector is transparent over the array, which has the layout of `[T; S]`.
+        Ok(unsafe { &*ptr })
+    }
+}
+
+impl<'a, T> Clone for CVect<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for CVect<'a, T> {}
+
+impl<'a, T> Debug for CVect<'a, T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<'a, T> From<&'a [T]> for CVect<'a, T> {
+    fn from(slice: &'a [T]) -> Self {
+        Self::new(slice)
+    }
+}
+
+impl<'a, T, S, D> From<&'a Vect<T, S, D>> for CVect<'a, T>
+where
+    S: Size,
+    D: ContiguousStorage<T>,
+{
+    fn from(vec: &'a Vect<T, S, D>) -> Self {
+        vec.as_c_vect()
+    }
+}
+
+/// Copies the elements into a vector with static length, if the length matches.
+impl<'a, T, S> TryFrom<CVect<'a, T>> for Vect<T, S>
+where
+    T: Clone,
+    S: Unsigned + Size,
+{
+    type Error = LengthError<CVect<'a, T>>;
+
+    fn try_from(view: CVect<'a, T>) -> Result<Self, Self::Error> {
+        if view.len == S::USIZE {
+            Ok(Vect {
+                data: Storage::from_exact_iter(view.as_slice().iter().cloned()),
+                _phantom: PhantomData,
+            })
+        } else {
+            Err(LengthError::new(S::USIZE, view.len, view))
+        }
+    }
+}
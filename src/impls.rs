@@ -2,47 +2,55 @@
 
 use crate::{
     common::*,
+    error::IndexError,
     size::{self, Dyn, Size},
+    storage::Storage,
     vect::Vect,
 };
 
-typ! {
-    pub fn Push<ty, size: Size>(Vect::<ty, size>: _) {
-        let new_size: Size = size::IncreaseOne(size);
-        Vect::<ty, new_size>
-    }
+// VectFactory
 
-    pub fn Pop<ty, size: Size>(Vect::<ty, size>: _) {
-        match size::DecreaseOne(size) {
-            Option::<Dyn> => Option::<Vect<ty, Dyn>>,
-            UTerm => Vect::<ty, UTerm>,
-            #[generics(uint: Unsigned, bit: Bit)]
-            UInt::<uint, bit> => {
-                let new_size = UInt::<uint, bit>;
-                Vect::<ty, new_size>
-            }
-        }
-    }
+mod sealed {
+    pub trait Sealed {}
 }
 
-// VectFactory
+/// Builds a vector from its storage in vector operations.
+///
+/// The trait is sealed and cannot be implemented outside this crate.
+pub trait VectFactory<T, D>: sealed::Sealed
+where
+    D: Storage<T>,
+{
+    /// Creates a vector without checking the length.
+    ///
+    /// # Safety
+    /// If `Self` has a static length, the length of `data` must be equal to it.
+    unsafe fn from_storage_unchecked(data: D) -> Self;
+}
 
-pub trait VectFactory<T> {
-    fn from_vec(vec: Vec<T>) -> Self;
+impl<T, S, D> sealed::Sealed for Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+{
 }
 
-impl<T, S> VectFactory<T> for Vect<T, S>
+impl<T, S, D> VectFactory<T, D> for Vect<T, S, D>
 where
     S: Size,
+    D: Storage<T>,
 {
-    fn from_vec(vec: Vec<T>) -> Self {
+    unsafe fn from_storage_unchecked(data: D) -> Self {
         Vect {
-            data: vec,
+            data,
             _phantom: PhantomData,
         }
     }
 }
 
+/// The storage `D` after resizing to `S`.
+type Resized<T, D, S> = <D as Storage<T>>::Resized<S>;
+
 // push
 pub use push::{PushImpl, PushImplOp};
 
@@ -55,18 +63,18 @@ mod push {
         fn impl_push(input: Input, elem: Item) -> Self::Output;
     }
 
-    impl<T, S> PushImpl<Vect<T, S>, T> for ()
+    impl<T, S, D> PushImpl<Vect<T, S, D>, T> for ()
     where
         S: Size,
-        (): Push<Vect<T, S>>,
-        PushOp<Vect<T, S>>: VectFactory<T>,
+        D: Storage<T>,
+        (): size::IncreaseOne<S>,
     {
-        type Output = PushOp<Vect<T, S>>;
+        type Output = Vect<T, size::IncreaseOneOp<S>, Resized<T, D, size::IncreaseOneOp<S>>>;
 
-        fn impl_push(input: Vect<T, S>, item: T) -> Self::Output {
-            let mut data = input.data;
-            data.push(item);
-            PushOp::<Vect<T, S>>::from_vec(data)
+        fn impl_push(input: Vect<T, S, D>, item: T) -> Self::Output {
+            let data = input.data.push::<size::IncreaseOneOp<S>>(item);
+            // SAFETY: the output length is the input length plus one
+            unsafe { Self::Output::from_storage_unchecked(data) }
         }
     }
 
@@ -85,14 +93,18 @@ mod pop {
         fn impl_pop(input: Input) -> Self::Output;
     }
 
-    impl<Input> PopImpl<Input> for ()
+    // The last element exists if and only if index zero is valid, which is checked
+    // in compile time for static lengths.
+    impl<T, S, D> PopImpl<Vect<T, S, D>> for ()
     where
-        (): PopPrivate<Input, PopOp<Input>> + Pop<Input>,
+        S: Size,
+        D: Storage<T>,
+        (): PopPrivate<Vect<T, S, D>, size::CheckIndexOp<S, U0>> + size::CheckIndex<S, U0>,
     {
-        type Output = <() as PopPrivate<Input, PopOp<Input>>>::Output;
+        type Output = <() as PopPrivate<Vect<T, S, D>, size::CheckIndexOp<S, U0>>>::Output;
 
-        fn impl_pop(input: Input) -> Self::Output {
-            <() as PopPrivate<Input, PopOp<Input>>>::impl_pop(input)
+        fn impl_pop(input: Vect<T, S, D>) -> Self::Output {
+            <() as PopPrivate<Vect<T, S, D>, size::CheckIndexOp<S, U0>>>::impl_pop(input)
         }
     }
 
@@ -103,38 +115,50 @@ mod pop {
         fn impl_pop(input: Input) -> Self::Output;
     }
 
-    impl<T, S, Output> PopPrivate<Vect<T, S>, Option<Output>> for ()
+    impl<T, S, D> PopPrivate<Vect<T, S, D>, Option<()>> for ()
     where
         S: Size,
-        Output: VectFactory<T>,
+        D: Storage<T>,
     {
-        type Output = Option<(Output, T)>;
-
-        fn impl_pop(input: Vect<T, S>) -> Self::Output {
-            let mut data = input.data;
-            let elem = data.pop()?;
-            Some((Output::from_vec(data), elem))
+        type Output = Result<(Vect<T, Dyn, Resized<T, D, Dyn>>, T), Vect<T, S, D>>;
+
+        fn impl_pop(input: Vect<T, S, D>) -> Self::Output {
+            match input.data.pop::<Dyn>() {
+                // SAFETY: the output length is dynamic
+                Ok((data, elem)) => Ok((unsafe { Vect::from_storage_unchecked(data) }, elem)),
+                Err(data) => Err(Vect {
+                    data,
+                    _phantom: PhantomData,
+                }),
+            }
         }
     }
 
-    impl<T, S1, S2> PopPrivate<Vect<T, S1>, Vect<T, S2>> for ()
+    impl<T, S, D> PopPrivate<Vect<T, S, D>, ()> for ()
     where
-        S1: Size,
-        S2: Size,
-        Vect<T, S2>: VectFactory<T>,
+        S: Size,
+        D: Storage<T>,
+        (): size::DecreaseOne<S>,
     {
-        type Output = (Vect<T, S2>, T);
-
-        fn impl_pop(input: Vect<T, S1>) -> Self::Output {
-            let mut data = input.data;
-            let elem = data.pop().unwrap();
-            (Vect::<T, S2>::from_vec(data), elem)
+        type Output = (
+            Vect<T, size::DecreaseOneOp<S>, Resized<T, D, size::DecreaseOneOp<S>>>,
+            T,
+        );
+
+        fn impl_pop(input: Vect<T, S, D>) -> Self::Output {
+            let (data, elem) = match input.data.pop::<size::DecreaseOneOp<S>>() {
+                Ok(output) => output,
+                Err(_) => unreachable!("the static length is positive"),
+            };
+            // SAFETY: the output length is the input length minus one
+            (unsafe { Vect::from_storage_unchecked(data) }, elem)
         }
     }
 }
 
 // get
 
+use get::GetPrivate;
 pub use get::{GetImpl, GetImplOp};
 
 mod get {
@@ -146,18 +170,19 @@ mod get {
         fn impl_get(input: &'a Input, index: Index) -> Self::Output;
     }
 
-    impl<'a, T, S, Index> GetImpl<'a, Vect<T, S>, Index> for ()
+    impl<'a, T, S, D, Index> GetImpl<'a, Vect<T, S, D>, Index> for ()
     where
         S: Size,
+        D: Storage<T>,
         Index: Size,
-        (): GetPrivate<'a, Vect<T, S>, Index, size::CheckIndexOp<S, Index>>
+        (): GetPrivate<'a, Vect<T, S, D>, Index, size::CheckIndexOp<S, Index>>
             + size::CheckIndex<S, Index>,
     {
         type Output =
-            <() as GetPrivate<'a, Vect<T, S>, Index, size::CheckIndexOp<S, Index>>>::Output;
+            <() as GetPrivate<'a, Vect<T, S, D>, Index, size::CheckIndexOp<S, Index>>>::Output;
 
-        fn impl_get(input: &'a Vect<T, S>, index: Index) -> Self::Output {
-            <() as GetPrivate<'a, Vect<T, S>, Index, size::CheckIndexOp<S, Index>>>::impl_get(
+        fn impl_get(input: &'a Vect<T, S, D>, index: Index) -> Self::Output {
+            <() as GetPrivate<'a, Vect<T, S, D>, Index, size::CheckIndexOp<S, Index>>>::impl_get(
                 input, index,
             )
         }
@@ -170,28 +195,31 @@ mod get {
         fn impl_get(input: &'a Input, index: Index) -> Self::Output;
     }
 
-    impl<'a, T, S, Index> GetPrivate<'a, Vect<T, S>, Index, ()> for ()
+    impl<'a, T, S, D, Index> GetPrivate<'a, Vect<T, S, D>, Index, ()> for ()
     where
         T: 'a,
         S: Size,
+        D: Storage<T>,
         Index: Unsigned + Size,
     {
         type Output = &'a T;
 
-        fn impl_get(input: &'a Vect<T, S>, _index: Index) -> Self::Output {
+        fn impl_get(input: &'a Vect<T, S, D>, _index: Index) -> Self::Output {
+            // SAFETY: the index is checked against the length in compile time
             unsafe { input.data.get_unchecked(Index::USIZE) }
         }
     }
 
-    impl<'a, T, S, Index> GetPrivate<'a, Vect<T, S>, Index, Option<()>> for ()
+    impl<'a, T, S, D, Index> GetPrivate<'a, Vect<T, S, D>, Index, Option<()>> for ()
     where
         T: 'a,
         S: Size,
+        D: Storage<T>,
         Index: Size,
     {
         type Output = Option<&'a T>;
 
-        fn impl_get(input: &'a Vect<T, S>, index: Index) -> Self::Output {
+        fn impl_get(input: &'a Vect<T, S, D>, index: Index) -> Self::Output {
             input.data.get(index.to_usize())
         }
     }
@@ -211,18 +239,21 @@ mod insert {
     }
     pub type InsertImplOp<Input, Index, Item> = <() as InsertImpl<Input, Index, Item>>::Output;
 
-    impl<S, Index, Item> InsertImpl<Vect<Item, S>, Index, Item> for ()
+    impl<S, D, Index, Item> InsertImpl<Vect<Item, S, D>, Index, Item> for ()
     where
         S: Size,
+        D: Storage<Item>,
         Index: Size,
         (): size::IncreaseOne<S> + size::CheckIndexInclusive<S, Index>,
     {
-        type Output = Vect<Item, size::IncreaseOneOp<S>>;
-
-        fn impl_insert(input: Vect<Item, S>, index: Index, item: Item) -> Self::Output {
-            let mut data = input.data;
-            data.insert(index.to_usize(), item);
-            <Self::Output as VectFactory<Item>>::from_vec(data)
+        type Output = Vect<Item, size::IncreaseOneOp<S>, Resized<Item, D, size::IncreaseOneOp<S>>>;
+
+        fn impl_insert(input: Vect<Item, S, D>, index: Index, item: Item) -> Self::Output {
+            let data = input
+                .data
+                .insert::<size::IncreaseOneOp<S>>(index.to_usize(), item);
+            // SAFETY: the output length is the input length plus one
+            unsafe { Self::Output::from_storage_unchecked(data) }
         }
     }
 }
@@ -241,21 +272,327 @@ mod remove {
     }
     pub type RemoveImplOp<Input, Index> = <() as RemoveImpl<Input, Index>>::Output;
 
-    impl<S, Index, Item> RemoveImpl<Vect<Item, S>, Index> for ()
+    impl<S, D, Index, Item> RemoveImpl<Vect<Item, S, D>, Index> for ()
     where
         S: Size,
+        D: Storage<Item>,
         Index: Size,
         (): size::DecreaseOne<S> + size::CheckIndex<S, Index>,
     {
-        type Output = (Vect<Item, size::DecreaseOneOp<S>>, Item);
-
-        fn impl_remove(input: Vect<Item, S>, index: Index) -> Self::Output {
-            let mut data = input.data;
-            let item = data.remove(index.to_usize());
-            (
-                <Vect<Item, size::DecreaseOneOp<S>> as VectFactory<Item>>::from_vec(data),
-                item,
+        type Output = (
+            Vect<Item, size::DecreaseOneOp<S>, Resized<Item, D, size::DecreaseOneOp<S>>>,
+            Item,
+        );
+
+        fn impl_remove(input: Vect<Item, S, D>, index: Index) -> Self::Output {
+            let (data, item) = input
+                .data
+                .remove::<size::DecreaseOneOp<S>>(index.to_usize());
+            // SAFETY: the output length is the input length minus one
+            (unsafe { Vect::from_storage_unchecked(data) }, item)
+        }
+    }
+}
+
+// split_at
+
+/// The output of [split_at](Vect::split_at), which holds the first `I` elements and the rest.
+pub type SplitAtOp<T, S, D, I> = (
+    Vect<T, I, Resized<T, D, I>>,
+    Vect<T, Diff<S, I>, Resized<T, D, Diff<S, I>>>,
+);
+
+// try_get
+
+pub use try_get::{TryGetImpl, TryGetImplOp};
+
+mod try_get {
+    use super::*;
+
+    /// Implements fallible element access on a vector.
+    pub trait TryGetImpl<'a, Input, Index> {
+        type Output;
+        fn impl_try_get(input: &'a Input, index: Index) -> Self::Output;
+    }
+
+    impl<'a, T, S, D, Index> TryGetImpl<'a, Vect<T, S, D>, Index> for ()
+    where
+        S: Size,
+        D: Storage<T>,
+        Index: Size,
+        (): TryGetPrivate<'a, Vect<T, S, D>, Index, size::CheckIndexOp<S, Index>>
+            + size::CheckIndex<S, Index>,
+    {
+        type Output =
+            <() as TryGetPrivate<'a, Vect<T, S, D>, Index, size::CheckIndexOp<S, Index>>>::Output;
+
+        fn impl_try_get(input: &'a Vect<T, S, D>, index: Index) -> Self::Output {
+            <() as TryGetPrivate<'a, Vect<T, S, D>, Index, size::CheckIndexOp<S, Index>>>::impl_try_get(
+                input, index,
             )
         }
     }
+
+    pub type TryGetImplOp<'a, Input, Index> = <() as TryGetImpl<'a, Input, Index>>::Output;
+
+    pub trait TryGetPrivate<'a, Input, Index, Out> {
+        type Output;
+        fn impl_try_get(input: &'a Input, index: Index) -> Self::Output;
+    }
+
+    impl<'a, T, S, D, Index> TryGetPrivate<'a, Vect<T, S, D>, Index, ()> for ()
+    where
+        T: 'a,
+        S: Size,
+        D: Storage<T>,
+        Index: Size,
+        (): GetPrivate<'a, Vect<T, S, D>, Index, (), Output = &'a T>,
+    {
+        type Output = &'a T;
+
+        fn impl_try_get(input: &'a Vect<T, S, D>, index: Index) -> Self::Output {
+            <() as GetPrivate<'a, Vect<T, S, D>, Index, ()>>::impl_get(input, index)
+        }
+    }
+
+    impl<'a, T, S, D, Index> TryGetPrivate<'a, Vect<T, S, D>, Index, Option<()>> for ()
+    where
+        T: 'a,
+        S: Size,
+        D: Storage<T>,
+        Index: Size,
+    {
+        type Output = Result<&'a T, IndexError>;
+
+        fn impl_try_get(input: &'a Vect<T, S, D>, index: Index) -> Self::Output {
+            let index = index.to_usize();
+            input
+                .data
+                .get(index)
+                .ok_or_else(|| IndexError::new(index, input.data.len(), ()))
+        }
+    }
+}
+
+// try_insert
+
+pub use try_insert::{TryInsertImpl, TryInsertImplOp};
+
+mod try_insert {
+    use super::*;
+
+    /// Implements fallible element insertion to a vector.
+    pub trait TryInsertImpl<Input, Index, Item> {
+        type Output;
+        fn impl_try_insert(input: Input, index: Index, item: Item) -> Self::Output;
+    }
+    pub type TryInsertImplOp<Input, Index, Item> =
+        <() as TryInsertImpl<Input, Index, Item>>::Output;
+
+    impl<S, D, Index, Item> TryInsertImpl<Vect<Item, S, D>, Index, Item> for ()
+    where
+        S: Size,
+        D: Storage<Item>,
+        Index: Size,
+        (): TryInsertPrivate<Vect<Item, S, D>, Index, Item, size::CheckIndexInclusiveOp<S, Index>>
+            + size::CheckIndexInclusive<S, Index>,
+    {
+        type Output = <() as TryInsertPrivate<
+            Vect<Item, S, D>,
+            Index,
+            Item,
+            size::CheckIndexInclusiveOp<S, Index>,
+        >>::Output;
+
+        fn impl_try_insert(input: Vect<Item, S, D>, index: Index, item: Item) -> Self::Output {
+            <() as TryInsertPrivate<
+                Vect<Item, S, D>,
+                Index,
+                Item,
+                size::CheckIndexInclusiveOp<S, Index>,
+            >>::impl_try_insert(input, index, item)
+        }
+    }
+
+    pub trait TryInsertPrivate<Input, Index, Item, Out> {
+        type Output;
+        fn impl_try_insert(input: Input, index: Index, item: Item) -> Self::Output;
+    }
+
+    impl<S, D, Index, Item> TryInsertPrivate<Vect<Item, S, D>, Index, Item, ()> for ()
+    where
+        S: Size,
+        D: Storage<Item>,
+        Index: Size,
+        (): InsertImpl<Vect<Item, S, D>, Index, Item>,
+    {
+        type Output = InsertImplOp<Vect<Item, S, D>, Index, Item>;
+
+        fn impl_try_insert(input: Vect<Item, S, D>, index: Index, item: Item) -> Self::Output {
+            <() as InsertImpl<Vect<Item, S, D>, Index, Item>>::impl_insert(input, index, item)
+        }
+    }
+
+    impl<S, D, Index, Item> TryInsertPrivate<Vect<Item, S, D>, Index, Item, Option<()>> for ()
+    where
+        S: Size,
+        D: Storage<Item>,
+        Index: Size,
+        (): InsertImpl<Vect<Item, S, D>, Index, Item>,
+    {
+        type Output = Result<
+            InsertImplOp<Vect<Item, S, D>, Index, Item>,
+            IndexError<(Vect<Item, S, D>, Item)>,
+        >;
+
+        fn impl_try_insert(input: Vect<Item, S, D>, index: Index, item: Item) -> Self::Output {
+            let len = input.data.len();
+            if index.to_usize() <= len {
+                Ok(
+                    <() as InsertImpl<Vect<Item, S, D>, Index, Item>>::impl_insert(
+                        input, index, item,
+                    ),
+                )
+            } else {
+                Err(IndexError::new(index.to_usize(), len, (input, item)))
+            }
+        }
+    }
+}
+
+// try_remove
+
+pub use try_remove::{TryRemoveImpl, TryRemoveImplOp};
+
+mod try_remove {
+    use super::*;
+
+    /// Implements fallible element removal from a vector.
+    pub trait TryRemoveImpl<Input, Index> {
+        type Output;
+        fn impl_try_remove(input: Input, index: Index) -> Self::Output;
+    }
+    pub type TryRemoveImplOp<Input, Index> = <() as TryRemoveImpl<Input, Index>>::Output;
+
+    impl<S, D, Index, Item> TryRemoveImpl<Vect<Item, S, D>, Index> for ()
+    where
+        S: Size,
+        D: Storage<Item>,
+        Index: Size,
+        (): TryRemovePrivate<Vect<Item, S, D>, Index, size::CheckIndexOp<S, Index>>
+            + size::CheckIndex<S, Index>,
+    {
+        type Output =
+            <() as TryRemovePrivate<Vect<Item, S, D>, Index, size::CheckIndexOp<S, Index>>>::Output;
+
+        fn impl_try_remove(input: Vect<Item, S, D>, index: Index) -> Self::Output {
+            <() as TryRemovePrivate<Vect<Item, S, D>, Index, size::CheckIndexOp<S, Index>>>::impl_try_remove(
+                input, index,
+            )
+        }
+    }
+
+    pub trait TryRemovePrivate<Input, Index, Out> {
+        type Output;
+        fn impl_try_remove(input: Input, index: Index) -> Self::Output;
+    }
+
+    impl<S, D, Index, Item> TryRemovePrivate<Vect<Item, S, D>, Index, ()> for ()
+    where
+        S: Size,
+        D: Storage<Item>,
+        Index: Size,
+        (): RemoveImpl<Vect<Item, S, D>, Index>,
+    {
+        type Output = RemoveImplOp<Vect<Item, S, D>, Index>;
+
+        fn impl_try_remove(input: Vect<Item, S, D>, index: Index) -> Self::Output {
+            <() as RemoveImpl<Vect<Item, S, D>, Index>>::impl_remove(input, index)
+        }
+    }
+
+    impl<S, D, Index, Item> TryRemovePrivate<Vect<Item, S, D>, Index, Option<()>> for ()
+    where
+        S: Size,
+        D: Storage<Item>,
+        Index: Size,
+        (): RemoveImpl<Vect<Item, S, D>, Index>,
+    {
+        type Output = Result<RemoveImplOp<Vect<Item, S, D>, Index>, IndexError<Vect<Item, S, D>>>;
+
+        fn impl_try_remove(input: Vect<Item, S, D>, index: Index) -> Self::Output {
+            let len = input.data.len();
+            if index.to_usize() < len {
+                Ok(<() as RemoveImpl<Vect<Item, S, D>, Index>>::impl_remove(
+                    input, index,
+                ))
+            } else {
+                Err(IndexError::new(index.to_usize(), len, input))
+            }
+        }
+    }
+}
+
+// dispatch
+
+#[cfg(feature = "alloc")]
+pub use dispatch::{DispatchImpl, StaticVisitor};
+
+#[cfg(feature = "alloc")]
+mod dispatch {
+    use super::*;
+
+    /// The visitor that receives a vector with static length from [Vect::dispatch].
+    pub trait StaticVisitor<T> {
+        type Output;
+
+        /// Visits a vector which length is known to be `S`.
+        fn visit<S>(self, vec: Vect<T, S>) -> Self::Output
+        where
+            S: Unsigned + Size;
+
+        /// Visits a vector which length exceeds the bound.
+        fn visit_dyn(self, vec: Vect<T, Dyn>) -> Self::Output;
+    }
+
+    /// Implements matching the runtime length against static sizes from `Max` down to `U0`.
+    pub trait DispatchImpl<T, Max, V>
+    where
+        V: StaticVisitor<T>,
+    {
+        fn impl_dispatch(input: Vect<T, Dyn>, visitor: V) -> V::Output;
+    }
+
+    impl<T, V> DispatchImpl<T, UTerm, V> for ()
+    where
+        V: StaticVisitor<T>,
+    {
+        fn impl_dispatch(input: Vect<T, Dyn>, visitor: V) -> V::Output {
+            match input.into_static::<UTerm>() {
+                Ok(vec) => visitor.visit(vec),
+                Err(err) => visitor.visit_dyn(err.into_data()),
+            }
+        }
+    }
+
+    impl<T, V, U, B> DispatchImpl<T, UInt<U, B>, V> for ()
+    where
+        V: StaticVisitor<T>,
+        U: Unsigned,
+        B: Bit,
+        UInt<U, B>: Size,
+        (): size::DecreaseOne<UInt<U, B>> + DispatchImpl<T, size::DecreaseOneOp<UInt<U, B>>, V>,
+    {
+        fn impl_dispatch(input: Vect<T, Dyn>, visitor: V) -> V::Output {
+            match input.into_static::<UInt<U, B>>() {
+                Ok(vec) => visitor.visit(vec),
+                Err(err) => {
+                    <() as DispatchImpl<T, size::DecreaseOneOp<UInt<U, B>>, V>>::impl_dispatch(
+                        err.into_data(),
+                        visitor,
+                    )
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,398 @@
+//! Iterators over the type-safe vector.
+
+#[cfg(feature = "alloc")]
+use crate::error::{DynLengthError, LengthError};
+use crate::{
+    common::*,
+    size::{self, Dyn, Size},
+    storage::{Storage, StorageFor},
+    vect::Vect,
+};
+
+/// The owning iterator created by [into_iter](IntoIterator::into_iter) on [Vect].
+pub struct IntoIter<T, D>
+where
+    D: Storage<T>,
+{
+    pub(crate) iter: D::IntoIter,
+}
+
+impl<T, D> IntoIter<T, D>
+where
+    D: Storage<T>,
+    D::IntoIter: AsRef<[T]>,
+{
+    /// Returns the remaining elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        self.iter.as_ref()
+    }
+}
+
+impl<T, D> Debug for IntoIter<T, D>
+where
+    D: Storage<T>,
+    D::IntoIter: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntoIter")
+            .field("iter", &self.iter)
+            .finish()
+    }
+}
+
+impl<T, D> Clone for IntoIter<T, D>
+where
+    D: Storage<T>,
+    D::IntoIter: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<T, D> Iterator for IntoIter<T, D>
+where
+    D: Storage<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, D> DoubleEndedIterator for IntoIter<T, D>
+where
+    D: Storage<T>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T, D> ExactSizeIterator for IntoIter<T, D>
+where
+    D: Storage<T>,
+{
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<T, D> FusedIterator for IntoIter<T, D> where D: Storage<T> {}
+
+/// The items given back by [from_iter_exact](Vect::from_iter_exact) on length mismatch.
+///
+/// It yields the items taken from the iterator, if any, followed by the rest of it.
+pub type ExactIterItems<T, D, I> =
+    iter::Chain<iter::Flatten<core::option::IntoIter<IntoIter<T, D>>>, iter::Peekable<I>>;
+
+impl<T, S, D> Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    /// Returns an iterator over the elements.
+    pub fn iter(&self) -> D::Iter<'_> {
+        self.data.iter()
+    }
+
+    /// Returns an iterator that allows modifying each element.
+    pub fn iter_mut(&mut self) -> D::IterMut<'_> {
+        self.data.iter_mut()
+    }
+}
+
+impl<T, S, D> IntoIterator for Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            iter: self.data.into_iter(),
+        }
+    }
+}
+
+impl<'a, T, S, D> IntoIterator for &'a Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    type Item = &'a T;
+    type IntoIter = D::Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, S, D> IntoIterator for &'a mut Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    type Item = &'a mut T;
+    type IntoIter = D::IterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, D> FromIterator<T> for Vect<T, Dyn, D>
+where
+    D: StorageFor<T, Dyn>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Vect {
+            data: D::from_exact_iter(iter),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Extend<T> for Vect<T, Dyn> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.data.extend(iter)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Extend<&'a T> for Vect<T, Dyn>
+where
+    T: 'a + Copy,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.data.extend(iter)
+    }
+}
+
+/// The extension trait that collects an iterator into a [Vect].
+#[cfg(feature = "alloc")]
+pub trait IteratorExt: Iterator {
+    /// Collects exactly `S` items into a vector with static length.
+    ///
+    /// It consumes at most `S + 1` items. If the iterator yields fewer or more items,
+    /// it returns the taken items in [LengthError]. When there are
+    /// more items, the reported length is `S + 1`.
+    fn collect_exact<S>(self) -> Result<Vect<Self::Item, S>, DynLengthError<Self::Item>>
+    where
+        Self: Sized,
+        S: Unsigned + Size,
+    {
+        let data: Vec<_> = self.take(S::USIZE + 1).collect();
+        Vect::from_vec(data).into_static()
+    }
+
+    /// Collects at most `S` items into a vector with dynamic length.
+    ///
+    /// It consumes at most `S + 1` items. If the iterator yields more than `S` items,
+    /// it returns the taken items in [LengthError] with reported length `S + 1`.
+    fn collect_at_most<S>(self) -> Result<Vect<Self::Item, Dyn>, DynLengthError<Self::Item>>
+    where
+        Self: Sized,
+        S: Unsigned + Size,
+    {
+        let data: Vec<_> = self.take(S::USIZE + 1).collect();
+        if data.len() <= S::USIZE {
+            Ok(Vect::from_vec(data))
+        } else {
+            Err(LengthError::new(S::USIZE, data.len(), Vect::from_vec(data)))
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> IteratorExt for I where I: Iterator {}
+
+// typed iterator
+
+/// The iterator adaptor which length is tracked at type level.
+///
+/// It is created by [typed_iter](Vect::typed_iter), [typed_iter_mut](Vect::typed_iter_mut)
+/// or [into_typed_iter](Vect::into_typed_iter). Every adaptor keeps the number of items,
+/// so that [collect_vect](TypedIter::collect_vect) returns a vector with length `S`
+/// without runtime check.
+///
+/// It does not implement [Iterator] to prevent items being taken out of the
+/// middle of the chain. Use [into_iter](IntoIterator::into_iter) to obtain a plain iterator.
+///
+/// ```rust
+/// use type_vec::Vect;
+/// use typenum::consts::*;
+///
+/// let lhs = Vect::<usize, U3>::from([1, 2, 3]);
+/// let rhs = Vect::<usize, U3>::from([4, 5, 6]);
+/// let sums: Vect<usize, U3> = lhs
+///     .typed_iter()
+///     .zip(rhs.typed_iter())
+///     .map(|(l, r)| l + r)
+///     .collect_vect();
+/// assert_eq!(sums, [5, 7, 9]);
+/// ```
+///
+/// Collecting into another length does not compile.
+///
+/// ```compile_fail,E0308
+/// use type_vec::Vect;
+/// use typenum::consts::*;
+///
+/// let vec = Vect::<usize, U3>::from([1, 2, 3]);
+/// let _: Vect<usize, U4> = vec.typed_iter().cloned().collect_vect();
+/// ```
+///
+/// Neither does zipping iterators of different lengths.
+///
+/// ```compile_fail,E0308
+/// use type_vec::Vect;
+/// use typenum::consts::*;
+///
+/// let lhs = Vect::<usize, U3>::from([1, 2, 3]);
+/// let rhs = Vect::<usize, U2>::from([4, 5]);
+/// lhs.typed_iter().zip(rhs.typed_iter());
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypedIter<I, S>
+where
+    I: Iterator,
+    S: Size,
+{
+    iter: I,
+    _phantom: PhantomData<S>,
+}
+
+impl<I, S> TypedIter<I, S>
+where
+    I: Iterator,
+    S: Size,
+{
+    fn new(iter: I) -> Self {
+        Self {
+            iter,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Maps each item by a closure.
+    pub fn map<F, B>(self, f: F) -> TypedIter<iter::Map<I, F>, S>
+    where
+        F: FnMut(I::Item) -> B,
+    {
+        TypedIter::new(self.iter.map(f))
+    }
+
+    /// Zips with another iterator of the same length.
+    pub fn zip<J>(self, other: TypedIter<J, S>) -> TypedIter<iter::Zip<I, J>, S>
+    where
+        J: Iterator,
+    {
+        TypedIter::new(self.iter.zip(other.iter))
+    }
+
+    /// Pairs each item with its index.
+    pub fn enumerate(self) -> TypedIter<iter::Enumerate<I>, S> {
+        TypedIter::new(self.iter.enumerate())
+    }
+
+    /// Reverses the iteration order.
+    pub fn rev(self) -> TypedIter<iter::Rev<I>, S>
+    where
+        I: DoubleEndedIterator,
+    {
+        TypedIter::new(self.iter.rev())
+    }
+
+    /// Appends another iterator. The length is the sum of both lengths.
+    pub fn chain<J, S2>(
+        self,
+        other: TypedIter<J, S2>,
+    ) -> TypedIter<iter::Chain<I, J>, size::SizeAddOp<S, S2>>
+    where
+        J: Iterator<Item = I::Item>,
+        S2: Size,
+        (): size::SizeAdd<S, S2>,
+    {
+        TypedIter::new(self.iter.chain(other.iter))
+    }
+
+    /// Clones each referenced item.
+    pub fn cloned<'a, T>(self) -> TypedIter<iter::Cloned<I>, S>
+    where
+        I: Iterator<Item = &'a T>,
+        T: 'a + Clone,
+    {
+        TypedIter::new(self.iter.cloned())
+    }
+
+    /// Copies each referenced item.
+    pub fn copied<'a, T>(self) -> TypedIter<iter::Copied<I>, S>
+    where
+        I: Iterator<Item = &'a T>,
+        T: 'a + Copy,
+    {
+        TypedIter::new(self.iter.copied())
+    }
+
+    /// Collects the items into a vector with length `S`.
+    pub fn collect_vect(self) -> Vect<I::Item, S> {
+        Vect {
+            data: Storage::from_exact_iter(self.iter),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<I, S> IntoIterator for TypedIter<I, S>
+where
+    I: Iterator,
+    S: Size,
+{
+    type Item = I::Item;
+    type IntoIter = I;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter
+    }
+}
+
+impl<T, S, D> Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    /// Returns an iterator over the elements which length is tracked at type level.
+    pub fn typed_iter(&self) -> TypedIter<D::Iter<'_>, S> {
+        TypedIter::new(self.iter())
+    }
+
+    /// Returns a mutable iterator over the elements which length is tracked at type level.
+    pub fn typed_iter_mut(&mut self) -> TypedIter<D::IterMut<'_>, S> {
+        TypedIter::new(self.iter_mut())
+    }
+
+    /// Converts to an owning iterator which length is tracked at type level.
+    pub fn into_typed_iter(self) -> TypedIter<IntoIter<T, D>, S> {
+        TypedIter::new(self.into_iter())
+    }
+}
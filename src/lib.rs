@@ -44,7 +44,7 @@
 //! it picks the more efficient implementation.
 //! Let's see the element accessing using [get](Vect::get). If the index is static,
 //! The index is checked against the length in compile time, and returns the element directly
-//! if it compiles. Otherwise, it returns an `Option<&T>` like usual [Vec].
+//! if it compiles. Otherwise, it returns an `Option<&T>` like usual `Vec`.
 //!
 //! ```rust
 //! use type_vec::Vect;
@@ -71,11 +71,57 @@
 //! [TYP type-level programming langauge](https://github.com/jerry73204/typ).
 //! It enables complex type-level computation done by simple Rusty syntax.
 //! Those interested can read the [TYP book](https://github.com/jerry73204/typ-book/).
+//!
+//! ## Features
+//! The crate supports `no_std` environments.
+//!
+//! - `std` (default): implements `std::error::Error` for the error types, and enables the
+//!   thread-safe `VectPool`. It enables `alloc`.
+//! - `alloc`: enables vectors with dynamic length and the heap-allocated storages, such as `Vec`.
+//!
+//! Without `alloc`, vectors with static length still work in the inline [Array] storage.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod builder;
+#[cfg(feature = "alloc")]
+pub mod capacity;
 pub(crate) mod common;
+mod convert;
+pub mod error;
+pub mod ffi;
 pub mod impls;
+pub mod iter;
+mod macros;
+#[cfg(feature = "std")]
+pub mod pool;
+pub mod predicate;
+pub mod secret;
 pub mod size;
+pub mod storage;
 pub mod vect;
 
+pub use builder::VectBuilder;
+#[cfg(feature = "alloc")]
+pub use capacity::CapVect;
+#[cfg(feature = "alloc")]
+pub use error::{DynLengthError, Error};
+pub use error::{IndexError, LengthError};
+pub use ffi::CVect;
+#[cfg(feature = "alloc")]
+pub use impls::StaticVisitor;
+#[cfg(feature = "alloc")]
+pub use iter::IteratorExt;
+pub use iter::TypedIter;
+#[cfg(feature = "std")]
+pub use pool::VectPool;
+pub use secret::SecretVect;
 pub use size::{Dyn, Size};
+pub use storage::{Array, ContiguousStorage, Storage, StorageFor};
 pub use vect::Vect;
+
+#[doc(hidden)]
+pub use typenum;
@@ -0,0 +1,223 @@
+//! Macros for writing type-level size expressions.
+
+/// Expands an arithmetic expression over sizes to a size type.
+///
+/// The expression consists of `+`, `*`, parentheses, integer literals and
+/// size types, including [Dyn](crate::Dyn) and generic parameters.
+/// It expands to the output aliases in the [size](mod@crate::size) module.
+/// For example, `size!(N * 2 + 1)` expands to
+/// `IncreaseOneOp<SizeMulOp<N, U2>>`. Operators are grouped from right to left.
+///
+/// The expression does not carry its own trait bounds. Use [size_bounds](crate::size_bounds)
+/// to add them to the `where` clause of a generic item.
+///
+/// ```rust
+/// use type_vec::{size, Dyn, Vect};
+/// use typenum::consts::*;
+///
+/// let vec: Vect<usize, size!(U2 * 2 + 1)> = Vect::<usize, U0>::new()
+///     .push(1)
+///     .push(2)
+///     .push(3)
+///     .push(4)
+///     .push(5);
+/// let _: Vect<usize, size!(U4 + Dyn)> = vec.into_dyn();
+/// ```
+#[macro_export]
+macro_rules! size {
+    (@sum [$($lhs:tt)*] + 1) => {
+        $crate::size::IncreaseOneOp<$crate::size!(@prod [] $($lhs)*)>
+    };
+    (@sum [$($lhs:tt)*] + $($rhs:tt)+) => {
+        $crate::size::SizeAddOp<$crate::size!(@prod [] $($lhs)*), $crate::size!($($rhs)+)>
+    };
+    (@sum [$($lhs:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::size!(@sum [$($lhs)* $token] $($rest)*)
+    };
+    (@sum [$($lhs:tt)*]) => {
+        $crate::size!(@prod [] $($lhs)*)
+    };
+    (@prod [$($lhs:tt)*] * $($rhs:tt)+) => {
+        $crate::size::SizeMulOp<$crate::size!(@factor $($lhs)*), $crate::size!(@prod [] $($rhs)+)>
+    };
+    (@prod [$($lhs:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::size!(@prod [$($lhs)* $token] $($rest)*)
+    };
+    (@prod [$($lhs:tt)*]) => {
+        $crate::size!(@factor $($lhs)*)
+    };
+    (@factor $($factor:tt)+) => {
+        $crate::__size_factor!($($factor)+)
+    };
+    ($($expr:tt)+) => {
+        $crate::size!(@sum [] $($expr)+)
+    };
+}
+
+/// Adds the trait bounds required by [size!](crate::size!) expressions to an item.
+///
+/// The expressions are listed in brackets before the item. The bounds are appended
+/// to the `where` clause of the item, or a new `where` clause is created.
+///
+/// ```rust
+/// use type_vec::{size, size_bounds, Dyn, Size, Vect};
+/// use typenum::{consts::*, Unsigned};
+///
+/// size_bounds! {
+///     [N * 2 + 1]
+///     fn mirror<T, N>(vec: Vect<T, N>, center: T) -> Vect<T, size!(N * 2 + 1)>
+///     where
+///         T: Clone,
+///         N: Size,
+///         size!(N * 2 + 1): Unsigned,
+///     {
+///         let mut data = vec.into_vec();
+///         let mirrored: Vec<T> = data.iter().rev().cloned().collect();
+///         data.push(center);
+///         data.extend(mirrored);
+///         Vect::<T, Dyn>::from_vec(data)
+///             .into_static()
+///             .unwrap_or_else(|_| unreachable!())
+///     }
+/// }
+///
+/// let vec = Vect::<usize, U0>::new().push(1).push(2);
+/// let vec: Vect<usize, U5> = mirror(vec, 3);
+/// assert_eq!(vec.into_vec(), vec![1, 2, 3, 2, 1]);
+/// ```
+#[macro_export]
+macro_rules! size_bounds {
+    // visit pending expressions and collect bounds
+    (@visit $item:tt [] [$($bounds:tt)*]) => {
+        $crate::size_bounds!(@emit [$($bounds)*] [] $item)
+    };
+    (@visit $item:tt [[$($expr:tt)*] $($pending:tt)*] $bounds:tt) => {
+        $crate::size_bounds!(@sum $item [$($pending)*] $bounds [] $($expr)*)
+    };
+    (@sum $item:tt [$($pending:tt)*] [$($bounds:tt)*] [$($lhs:tt)*] + 1) => {
+        $crate::size_bounds!(
+            @visit $item
+            [[$($lhs)*] $($pending)*]
+            [$($bounds)* (): $crate::size::IncreaseOne<$crate::size!($($lhs)*)>,]
+        )
+    };
+    (@sum $item:tt [$($pending:tt)*] [$($bounds:tt)*] [$($lhs:tt)*] + $($rhs:tt)+) => {
+        $crate::size_bounds!(
+            @visit $item
+            [[$($lhs)*] [$($rhs)+] $($pending)*]
+            [$($bounds)* (): $crate::size::SizeAdd<$crate::size!($($lhs)*), $crate::size!($($rhs)+)>,]
+        )
+    };
+    (@sum $item:tt $pending:tt $bounds:tt [$($lhs:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::size_bounds!(@sum $item $pending $bounds [$($lhs)* $token] $($rest)*)
+    };
+    (@sum $item:tt $pending:tt $bounds:tt [$($lhs:tt)*]) => {
+        $crate::size_bounds!(@prod $item $pending $bounds [] $($lhs)*)
+    };
+    (@prod $item:tt [$($pending:tt)*] [$($bounds:tt)*] [$($lhs:tt)*] * $($rhs:tt)+) => {
+        $crate::size_bounds!(
+            @visit $item
+            [[$($lhs)*] [$($rhs)+] $($pending)*]
+            [$($bounds)* (): $crate::size::SizeMul<$crate::size!($($lhs)*), $crate::size!($($rhs)+)>,]
+        )
+    };
+    (@prod $item:tt $pending:tt $bounds:tt [$($lhs:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::size_bounds!(@prod $item $pending $bounds [$($lhs)* $token] $($rest)*)
+    };
+    (@prod $item:tt [$($pending:tt)*] $bounds:tt [($($inner:tt)+)]) => {
+        $crate::size_bounds!(@visit $item [[$($inner)+] $($pending)*] $bounds)
+    };
+    (@prod $item:tt $pending:tt $bounds:tt [$($factor:tt)*]) => {
+        $crate::size_bounds!(@visit $item $pending $bounds)
+    };
+    // emit the item with bounds
+    (@emit [$($bounds:tt)*] [$($head:tt)*] [where $($tail:tt)*]) => {
+        $($head)* where $($bounds)* $($tail)*
+    };
+    (@emit [$($bounds:tt)*] [$($head:tt)*] [{ $($body:tt)* }]) => {
+        $($head)* where $($bounds)* { $($body)* }
+    };
+    (@emit $bounds:tt [$($head:tt)*] [$token:tt $($tail:tt)*]) => {
+        $crate::size_bounds!(@emit $bounds [$($head)* $token] [$($tail)*])
+    };
+    ($([$($expr:tt)*])+ $($item:tt)+) => {
+        $crate::size_bounds!(@visit [$($item)+] [$([$($expr)*])+] [])
+    };
+}
+
+/// Expands a single factor in [size](crate::size) expressions.
+///
+/// Literals up to 64 expand to the named constants, which keep `where` clauses
+/// in generic items resolvable. Larger literals fall back to the `U<N>` alias.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __size_factor {
+    (($($inner:tt)+)) => { $crate::size!($($inner)+) };
+    (0) => { $crate::typenum::U0 };
+    (1) => { $crate::typenum::U1 };
+    (2) => { $crate::typenum::U2 };
+    (3) => { $crate::typenum::U3 };
+    (4) => { $crate::typenum::U4 };
+    (5) => { $crate::typenum::U5 };
+    (6) => { $crate::typenum::U6 };
+    (7) => { $crate::typenum::U7 };
+    (8) => { $crate::typenum::U8 };
+    (9) => { $crate::typenum::U9 };
+    (10) => { $crate::typenum::U10 };
+    (11) => { $crate::typenum::U11 };
+    (12) => { $crate::typenum::U12 };
+    (13) => { $crate::typenum::U13 };
+    (14) => { $crate::typenum::U14 };
+    (15) => { $crate::typenum::U15 };
+    (16) => { $crate::typenum::U16 };
+    (17) => { $crate::typenum::U17 };
+    (18) => { $crate::typenum::U18 };
+    (19) => { $crate::typenum::U19 };
+    (20) => { $crate::typenum::U20 };
+    (21) => { $crate::typenum::U21 };
+    (22) => { $crate::typenum::U22 };
+    (23) => { $crate::typenum::U23 };
+    (24) => { $crate::typenum::U24 };
+    (25) => { $crate::typenum::U25 };
+    (26) => { $crate::typenum::U26 };
+    (27) => { $crate::typenum::U27 };
+    (28) => { $crate::typenum::U28 };
+    (29) => { $crate::typenum::U29 };
+    (30) => { $crate::typenum::U30 };
+    (31) => { $crate::typenum::U31 };
+    (32) => { $crate::typenum::U32 };
+    (33) => { $crate::typenum::U33 };
+    (34) => { $crate::typenum::U34 };
+    (35) => { $crate::typenum::U35 };
+    (36) => { $crate::typenum::U36 };
+    (37) => { $crate::typenum::U37 };
+    (38) => { $crate::typenum::U38 };
+    (39) => { $crate::typenum::U39 };
+    (40) => { $crate::typenum::U40 };
+    (41) => { $crate::typenum::U41 };
+    (42) => { $crate::typenum::U42 };
+    (43) => { $crate::typenum::U43 };
+    (44) => { $crate::typenum::U44 };
+    (45) => { $crate::typenum::U45 };
+    (46) => { $crate::typenum::U46 };
+    (47) => { $crate::typenum::U47 };
+    (48) => { $crate::typenum::U48 };
+    (49) => { $crate::typenum::U49 };
+    (50) => { $crate::typenum::U50 };
+    (51) => { $crate::typenum::U51 };
+    (52) => { $crate::typenum::U52 };
+    (53) => { $crate::typenum::U53 };
+    (54) => { $crate::typenum::U54 };
+    (55) => { $crate::typenum::U55 };
+    (56) => { $crate::typenum::U56 };
+    (57) => { $crate::typenum::U57 };
+    (58) => { $crate::typenum::U58 };
+    (59) => { $crate::typenum::U59 };
+    (60) => { $crate::typenum::U60 };
+    (61) => { $crate::typenum::U61 };
+    (62) => { $crate::typenum::U62 };
+    (63) => { $crate::typenum::U63 };
+    (64) => { $crate::typenum::U64 };
+    ($value:literal) => { $crate::typenum::U<$value> };
+    ($ty:ty) => { $ty };
+}
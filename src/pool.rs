@@ -0,0 +1,360 @@
+//! The buffer pool that recycles the allocations of vectors.
+//!
+//! A [VectPool] hands out empty vectors in the [Pooled] storage. The
+//! storage gives its buffer back to the pool when it is dropped, so that the next
+//! vector taken from the pool reuses the allocation. The pool and the vectors can be
+//! sent to and shared between threads, such as the workers of a server.
+//!
+//! ```rust
+//! use type_vec::{pool::VectPool, Dyn, Vect};
+//! use typenum::consts::*;
+//!
+//! let pool = VectPool::<u8>::new();
+//!
+//! let vec = pool.with_capacity(1500).push(1).push(2);
+//! let ptr = vec.as_ptr();
+//! drop(vec);
+//!
+//! // the buffer is reused
+//! let vec = pool.empty_dyn().push(3);
+//! assert_eq!(vec.as_ptr(), ptr);
+//!
+//! // vectors in other storages can give their buffers to the pool as well
+//! let vec = Vect::<u8, Dyn>::with_capacity(64).push(4);
+//! vec.into_pool(&pool);
+//! assert_eq!(pool.len(), 1);
+//!
+//! // buffers are returned from other threads
+//! let vec = pool.with_capacity(64).push(5);
+//! std::thread::spawn(move || drop(vec)).join().unwrap();
+//! assert_eq!(pool.len(), 1);
+//! ```
+
+use crate::{
+    common::*,
+    size::{Dyn, Size},
+    storage::{ContiguousStorage, Storage, StorageFor},
+    vect::Vect,
+};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// The pool of recycled buffers.
+///
+/// The pool keeps at most [limit](VectPool::limit) buffers, and drops the buffers given
+/// back beyond it, so that a burst of vectors does not keep its peak memory.
+/// Cloning the pool gives another handle to the same buffers.
+pub struct VectPool<T> {
+    buffers: Arc<Mutex<Vec<Vec<T>>>>,
+    limit: usize,
+}
+
+impl<T> VectPool<T> {
+    /// The number of buffers kept by a pool created by [new](VectPool::new).
+    pub const DEFAULT_LIMIT: usize = 64;
+
+    /// Creates an empty pool that keeps up to [DEFAULT_LIMIT](VectPool::DEFAULT_LIMIT)
+    /// buffers.
+    pub fn new() -> Self {
+        Self::with_limit(Self::DEFAULT_LIMIT)
+    }
+
+    /// Creates an empty pool that keeps up to `limit` buffers.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            buffers: Arc::new(Mutex::new(vec![])),
+            limit,
+        }
+    }
+
+    /// Gets the maximum number of buffers kept in the pool.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Gets the number of buffers kept in the pool.
+    pub fn len(&self) -> usize {
+        self.buffers().len()
+    }
+
+    /// Returns true if the pool keeps no buffers.
+    pub fn is_empty(&self) -> bool {
+        self.buffers().is_empty()
+    }
+
+    /// Drops the buffers kept in the pool.
+    pub fn clear(&self) {
+        self.buffers().clear()
+    }
+
+    /// Creates an empty vector with static length from a recycled buffer.
+    pub fn empty(&self) -> Vect<T, U0, Pooled<T>> {
+        self.with_capacity(0)
+    }
+
+    /// Creates an empty vector with dynamic length from a recycled buffer.
+    pub fn empty_dyn(&self) -> Vect<T, Dyn, Pooled<T>> {
+        self.with_capacity_dyn(0)
+    }
+
+    /// Creates an empty vector with static length that holds at least `capacity` elements.
+    pub fn with_capacity(&self, capacity: usize) -> Vect<T, U0, Pooled<T>> {
+        Vect {
+            data: self.take(capacity),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates an empty vector with dynamic length that holds at least `capacity` elements.
+    pub fn with_capacity_dyn(&self, capacity: usize) -> Vect<T, Dyn, Pooled<T>> {
+        Vect {
+            data: self.take(capacity),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Puts the buffer of a [Vec] into the pool. The elements are dropped.
+    ///
+    /// The buffer is dropped as well if the pool is full.
+    pub fn recycle(&self, mut vec: Vec<T>) {
+        vec.clear();
+        if vec.capacity() == 0 {
+            return;
+        }
+        let mut buffers = self.buffers();
+        if buffers.len() < self.limit {
+            Vec::push(&mut buffers, vec);
+        }
+    }
+
+    fn take(&self, capacity: usize) -> Pooled<T> {
+        let mut vec = Vec::pop(&mut self.buffers()).unwrap_or_default();
+        vec.reserve(capacity);
+        Pooled {
+            vec,
+            pool: Some(self.clone()),
+        }
+    }
+
+    /// Locks the buffers. The buffers hold no elements, so they are still usable after
+    /// a panic in another thread.
+    fn buffers(&self) -> MutexGuard<'_, Vec<Vec<T>>> {
+        self.buffers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Clone for VectPool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            buffers: self.buffers.clone(),
+            limit: self.limit,
+        }
+    }
+}
+
+impl<T> Default for VectPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for VectPool<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VectPool")
+            .field("len", &self.len())
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<T, S, D> Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    /// Drops the elements and puts the buffer into the pool.
+    ///
+    /// Only the storages that own a heap buffer, such as [Vec], give it to the pool.
+    /// The others, such as the inline [Array](crate::storage::Array), just drop the elements.
+    pub fn into_pool(self, pool: &VectPool<T>) {
+        if let Some(vec) = self.data.into_buffer() {
+            pool.recycle(vec)
+        }
+    }
+}
+
+// Pooled
+
+/// The [Vec] storage that gives its buffer back to a [VectPool] on drop.
+///
+/// The storage built by [from_exact_iter](Storage::from_exact_iter) or
+/// [from_vec](Storage::from_vec), such as by [Vect::with_capacity],
+/// belongs to no pool. Converting into a [Vec] or an owning iterator detaches the
+/// buffer from the pool. Cloning takes the buffer of the clone from the same pool.
+pub struct Pooled<T> {
+    vec: Vec<T>,
+    pool: Option<VectPool<T>>,
+}
+
+impl<T> Pooled<T> {
+    /// Gets the pool the buffer is given back to.
+    pub fn pool(&self) -> Option<&VectPool<T>> {
+        self.pool.as_ref()
+    }
+}
+
+impl<T> Drop for Pooled<T> {
+    fn drop(&mut self) {
+        if let Some(pool) = &self.pool {
+            pool.recycle(mem::take(&mut self.vec));
+        }
+    }
+}
+
+impl<T> Clone for Pooled<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut data = match &self.pool {
+            Some(pool) => pool.take(self.vec.len()),
+            None => Self::from_vec(Vec::with_capacity(self.vec.len())),
+        };
+        data.vec.extend_from_slice(&self.vec);
+        data
+    }
+}
+
+impl<T> Debug for Pooled<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.vec, f)
+    }
+}
+
+unsafe impl<T> Storage<T> for Pooled<T> {
+    type Resized<S: Size> = Pooled<T>;
+    type IntoIter = vec::IntoIter<T>;
+
+    type Iter<'a>
+        = slice::Iter<'a, T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    type IterMut<'a>
+        = slice::IterMut<'a, T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn from_exact_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self::from_vec(iter.into_iter().collect())
+    }
+
+    fn from_vec(vec: Vec<T>) -> Self {
+        Self { vec, pool: None }
+    }
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(Storage::into_vec(self))
+    }
+
+    fn into_vec(mut self) -> Vec<T> {
+        mem::take(&mut self.vec)
+    }
+
+    fn into_buffer(self) -> Option<Vec<T>> {
+        Some(Storage::into_vec(self))
+    }
+
+    fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        self.vec.get(index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.vec.get_mut(index)
+    }
+
+    unsafe fn get_unchecked(&self, index: usize) -> &T {
+        self.vec.get_unchecked(index)
+    }
+
+    unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        self.vec.get_unchecked_mut(index)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.vec.iter()
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        self.vec.iter_mut()
+    }
+
+    fn hash_elements<H>(&self, state: &mut H)
+    where
+        T: Hash,
+        H: Hasher,
+    {
+        self.vec.hash(state)
+    }
+
+    fn push<S>(mut self, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        Vec::push(&mut self.vec, item);
+        self
+    }
+
+    fn pop<S>(mut self) -> Result<(Self::Resized<S>, T), Self>
+    where
+        S: Size,
+    {
+        match Vec::pop(&mut self.vec) {
+            Some(item) => Ok((self, item)),
+            None => Err(self),
+        }
+    }
+
+    fn insert<S>(mut self, index: usize, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        Vec::insert(&mut self.vec, index, item);
+        self
+    }
+
+    fn remove<S>(mut self, index: usize) -> (Self::Resized<S>, T)
+    where
+        S: Size,
+    {
+        let item = Vec::remove(&mut self.vec, index);
+        (self, item)
+    }
+}
+
+unsafe impl<T> ContiguousStorage<T> for Pooled<T> {
+    fn as_slice(&self) -> &[T] {
+        &self.vec
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.vec
+    }
+}
+
+impl<T, S> StorageFor<T, S> for Pooled<T> where S: Size {}
@@ -0,0 +1,138 @@
+//! Type-level predicates on sizes for use in `where` clauses.
+//!
+//! Every predicate comes in two forms.
+//! The bit form, such as [SizeEq], computes a [Bit] and can be
+//! read through its `*Op` alias. The bound form, such as [AssertSizeEq],
+//! is only implemented when the predicate holds, so that `(): AssertSizeEq<A, B>`
+//! rejects the call in compile time otherwise.
+//!
+//! A dynamic size is compatible with any size. The comparison predicates hold whenever
+//! either operand is [Dyn](crate::Dyn), since the check is deferred to runtime.
+//!
+//! ```rust
+//! use type_vec::{predicate::AssertSizeEq, Size, Vect};
+//! use typenum::consts::*;
+//!
+//! fn zip_sum<A, B>(lhs: Vect<usize, A>, rhs: Vect<usize, B>) -> Vec<usize>
+//! where
+//!     A: Size,
+//!     B: Size,
+//!     (): AssertSizeEq<A, B>,
+//! {
+//!     lhs.into_vec()
+//!         .into_iter()
+//!         .zip(rhs.into_vec())
+//!         .map(|(l, r)| l + r)
+//!         .collect()
+//! }
+//!
+//! let lhs = Vect::<usize, U0>::new().push(1).push(2);
+//! let rhs = Vect::<usize, U0>::new().push(3).push(4);
+//! assert_eq!(zip_sum(lhs, rhs), vec![4, 6]);
+//! ```
+//!
+//! The call does not compile if the lengths are mismatched.
+//!
+//! ```compile_fail,E0271
+//! # use type_vec::{predicate::AssertSizeEq, Size, Vect};
+//! # use typenum::consts::*;
+//! # fn zip_sum<A, B>(lhs: Vect<usize, A>, rhs: Vect<usize, B>)
+//! # where
+//! #     A: Size,
+//! #     B: Size,
+//! #     (): AssertSizeEq<A, B>,
+//! # {
+//! # }
+//! zip_sum(Vect::<usize, U0>::new().push(1), Vect::<usize, U0>::new());
+//! ```
+//!
+//! Likewise, [AssertStatic] rejects dynamic sizes, and
+//! [AssertSizeLe] rejects sizes over the bound.
+//!
+//! ```compile_fail,E0277
+//! use type_vec::{predicate::AssertStatic, Dyn, Size, Vect};
+//!
+//! fn static_len<S>(_vec: &Vect<usize, S>) -> usize
+//! where
+//!     S: Size + typenum::Unsigned,
+//!     (): AssertStatic<S>,
+//! {
+//!     S::USIZE
+//! }
+//!
+//! static_len(&Vect::<usize, Dyn>::new());
+//! ```
+//!
+//! ```compile_fail,E0271
+//! use type_vec::{predicate::AssertSizeLe, Size, Vect};
+//! use typenum::consts::*;
+//!
+//! fn fits<S, Cap>(_vec: &Vect<usize, S>)
+//! where
+//!     S: Size,
+//!     Cap: Size,
+//!     (): AssertSizeLe<S, Cap>,
+//! {
+//! }
+//!
+//! fits::<_, U1>(&Vect::<usize, U0>::new().push(1).push(2));
+//! ```
+
+use crate::{
+    common::*,
+    size::{IsDyn, Size},
+};
+
+typ! {
+    pub fn IsStatic<size>(size: Size) -> Bit {
+        !IsDyn(size)
+    }
+
+    pub fn SizeEq<lhs, rhs>(lhs: Size, rhs: Size) -> Bit {
+        if IsDyn(lhs) || IsDyn(rhs) {
+            true
+        } else {
+            lhs == rhs
+        }
+    }
+
+    pub fn SizeLt<lhs, rhs>(lhs: Size, rhs: Size) -> Bit {
+        if IsDyn(lhs) || IsDyn(rhs) {
+            true
+        } else {
+            lhs < rhs
+        }
+    }
+
+    pub fn SizeLe<lhs, rhs>(lhs: Size, rhs: Size) -> Bit {
+        if IsDyn(lhs) || IsDyn(rhs) {
+            true
+        } else {
+            lhs <= rhs
+        }
+    }
+
+    pub fn AssertStatic<size>(size: Size) {
+        match IsStatic(size) {
+            B1 => (),
+        }
+    }
+
+    pub fn AssertSizeEq<lhs, rhs>(lhs: Size, rhs: Size) {
+        match SizeEq(lhs, rhs) {
+            B1 => (),
+        }
+    }
+
+    pub fn AssertSizeLt<lhs, rhs>(lhs: Size, rhs: Size) {
+        match SizeLt(lhs, rhs) {
+            B1 => (),
+        }
+    }
+
+    pub fn AssertSizeLe<lhs, rhs>(lhs: Size, rhs: Size) {
+        match SizeLe(lhs, rhs) {
+            B1 => (),
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Vectors that hold secrets, such as keys and nonces.
+//!
+//! A [SecretVect] wipes its elements with volatile writes when it is dropped,
+//! and wipes the old buffer whenever an operation moves the elements to a new one.
+//! It compares in constant time and does not print its elements.
+//!
+//! ```rust
+//! use type_vec::{SecretVect, Vect};
+//! use typenum::consts::*;
+//!
+//! let key = SecretVect::new(Vect::<u8, U4>::from_fn(|index| index as u8));
+//! assert_eq!(key.get(U3::new()), &3);
+//! assert_eq!(format!("{:?}", key), "SecretVect { len: 4, .. }");
+//!
+//! // split the key in compile-time checked halves
+//! let (enc_key, mac_key) = key.split_at(U2::new());
+//! assert_eq!(enc_key, SecretVect::new(Vect::from([0u8, 1])));
+//! assert_eq!(mac_key.expose_secret(), &[2, 3]);
+//! ```
+
+use crate::{
+    common::*,
+    impls,
+    size::{self, IntoSize, Size},
+    storage::{Array, ContiguousStorage, InlineLen, Storage},
+    vect::Vect,
+};
+use core::{hint, sync::atomic};
+
+/// The element type that can be kept in a [SecretVect].
+///
+/// It is implemented for the primitive integers, for which the zero value is valid.
+pub trait SecretElement: Copy + sealed::Sealed {
+    #[doc(hidden)]
+    const ZERO: Self;
+
+    #[doc(hidden)]
+    fn to_bits(self) -> u128;
+}
+
+macro_rules! impl_secret_element {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl SecretElement for $ty {
+                const ZERO: Self = 0;
+
+                fn to_bits(self) -> u128 {
+                    self as u128
+                }
+            }
+        )*
+    };
+}
+
+impl_secret_element!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// The storage that can be wiped by a [SecretVect].
+///
+/// It is implemented for [Array] and `Vec`.
+///
+/// # Safety
+/// The storage must wipe every element it has ever held, including the spare capacity.
+pub unsafe trait SecretStorage<T>: ContiguousStorage<T> + sealed::Sealed
+where
+    T: SecretElement,
+{
+    #[doc(hidden)]
+    fn zeroize(&mut self);
+
+    /// Appends an element, leaving the elements in `self` to be wiped.
+    #[doc(hidden)]
+    fn push_secret<S>(&mut self, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        Self::Resized::<S>::from_exact_iter(self.as_slice().iter().copied().chain(iter::once(item)))
+    }
+}
+
+unsafe impl<T, N> SecretStorage<T> for Array<T, N>
+where
+    T: SecretElement,
+    N: InlineLen,
+{
+    fn zeroize(&mut self) {
+        let slice = self.as_mut_slice();
+        zero_volatile(slice.as_mut_ptr(), slice.len());
+    }
+}
+
+/// The buffer is reallocated by hand, so that the old buffer is wiped before it is freed.
+#[cfg(feature = "alloc")]
+unsafe impl<T> SecretStorage<T> for Vec<T>
+where
+    T: SecretElement,
+{
+    fn zeroize(&mut self) {
+        zero_volatile(self.as_mut_ptr(), self.capacity());
+    }
+
+    fn push_secret<S>(&mut self, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        let mut vec = if self.len() == self.capacity() {
+            let mut vec = Vec::with_capacity((self.capacity() * 2).max(4));
+            vec.extend_from_slice(self);
+            vec
+        } else {
+            mem::take(self)
+        };
+        Vec::push(&mut vec, item);
+        vec
+    }
+}
+
+/// Overwrites `len` elements from `ptr` with zeros that the compiler cannot elide.
+fn zero_volatile<T>(ptr: *mut T, len: usize)
+where
+    T: SecretElement,
+{
+    for index in 0..len {
+        // SAFETY: the memory is allocated for `len` elements, and the zero value is valid.
+        unsafe { ptr::write_volatile(ptr.add(index), T::ZERO) };
+    }
+    atomic::compiler_fence(atomic::Ordering::SeqCst);
+}
+
+/// The output of [split_at](SecretVect::split_at).
+pub type SplitAtOp<T, S, D, I> = (
+    SecretVect<T, I, <D as Storage<T>>::Resized<I>>,
+    SecretVect<T, Diff<S, I>, <D as Storage<T>>::Resized<Diff<S, I>>>,
+);
+
+/// The vector of secret elements, which is wiped on drop.
+///
+/// The elements can only be read through typed accesses, such as [get](SecretVect::get),
+/// or through [expose_secret](SecretVect::expose_secret).
+/// Moving the vector with inline storage may leave copies that are not wiped, so keep
+/// it in place or in a heap storage for the lifetime of the secret.
+pub struct SecretVect<T, S, D = <S as Size>::DefaultStorage<T>>
+where
+    T: SecretElement,
+    S: Size,
+    D: SecretStorage<T>,
+{
+    vect: Vect<T, S, D>,
+}
+
+impl<T, S, D> SecretVect<T, S, D>
+where
+    T: SecretElement,
+    S: Size,
+    D: SecretStorage<T>,
+{
+    /// Takes a vector as a secret.
+    pub fn new(vect: Vect<T, S, D>) -> Self {
+        Self { vect }
+    }
+
+    /// Gets the number of elements.
+    pub fn len(&self) -> usize {
+        self.vect.data.len()
+    }
+
+    /// Returns true if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.vect.data.is_empty()
+    }
+
+    /// Returns a reference to an element depending on the index, like [Vect::get].
+    pub fn get<'a, I>(&'a self, index: I) -> impls::GetImplOp<'a, Vect<T, S, D>, I::Output>
+    where
+        I: IntoSize,
+        (): impls::GetImpl<'a, Vect<T, S, D>, I::Output>,
+    {
+        self.vect.get(index)
+    }
+
+    /// Gives access to the vector holding the secret.
+    pub fn expose_secret(&self) -> &Vect<T, S, D> {
+        &self.vect
+    }
+
+    /// Gives mutable access to the elements of the secret.
+    pub fn expose_secret_mut(&mut self) -> &mut [T] {
+        self.vect.data.as_mut_slice()
+    }
+
+    /// Appends an element to the end of the vector, and wipes the old buffer.
+    pub fn push(
+        mut self,
+        item: T,
+    ) -> SecretVect<T, size::IncreaseOneOp<S>, D::Resized<size::IncreaseOneOp<S>>>
+    where
+        (): size::IncreaseOne<S>,
+        D::Resized<size::IncreaseOneOp<S>>: SecretStorage<T>,
+    {
+        let data = self.vect.data.push_secret(item);
+        SecretVect {
+            vect: Vect {
+                data,
+                _phantom: PhantomData,
+            },
+        }
+    }
+
+    /// Splits the secret into the first `I` elements and the rest, and wipes the old buffer.
+    ///
+    /// The index is static. It does not compile if the index exceeds the length.
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::{SecretVect, Vect};
+    /// use typenum::consts::*;
+    ///
+    /// let key = SecretVect::new(Vect::<u8, U4>::from([1, 2, 3, 4]));
+    /// let (left, right) = key.split_at(U5::new());
+    /// ```
+    pub fn split_at<I>(self, _index: I) -> SplitAtOp<T, S, D, I>
+    where
+        S: Unsigned + Sub<I>,
+        I: Unsigned + Size,
+        Diff<S, I>: Size,
+        D::Resized<I>: SecretStorage<T>,
+        D::Resized<Diff<S, I>>: SecretStorage<T>,
+    {
+        let (left, right) = self.vect.data.as_slice().split_at(I::USIZE);
+        let left = Vect {
+            data: Storage::from_exact_iter(left.iter().copied()),
+            _phantom: PhantomData,
+        };
+        let right = Vect {
+            data: Storage::from_exact_iter(right.iter().copied()),
+            _phantom: PhantomData,
+        };
+        (SecretVect::new(left), SecretVect::new(right))
+    }
+}
+
+impl<T, S, D> From<Vect<T, S, D>> for SecretVect<T, S, D>
+where
+    T: SecretElement,
+    S: Size,
+    D: SecretStorage<T>,
+{
+    fn from(vect: Vect<T, S, D>) -> Self {
+        Self::new(vect)
+    }
+}
+
+impl<T, S, D> Drop for SecretVect<T, S, D>
+where
+    T: SecretElement,
+    S: Size,
+    D: SecretStorage<T>,
+{
+    fn drop(&mut self) {
+        self.vect.data.zeroize();
+    }
+}
+
+impl<T, S, D> Clone for SecretVect<T, S, D>
+where
+    T: SecretElement,
+    S: Size,
+    D: SecretStorage<T> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self::new(self.vect.clone())
+    }
+}
+
+/// Prints the length only.
+impl<T, S, D> Debug for SecretVect<T, S, D>
+where
+    T: SecretElement,
+    S: Size,
+    D: SecretStorage<T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretVect")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Compares in constant time with respect to the elements. The lengths are not secret.
+impl<T, S1, S2, D1, D2> PartialEq<SecretVect<T, S2, D2>> for SecretVect<T, S1, D1>
+where
+    T: SecretElement,
+    S1: Size,
+    S2: Size,
+    D1: SecretStorage<T>,
+    D2: SecretStorage<T>,
+{
+    fn eq(&self, other: &SecretVect<T, S2, D2>) -> bool {
+        let lhs = self.vect.data.as_slice();
+        let rhs = other.vect.data.as_slice();
+        if lhs.len() != rhs.len() {
+            return false;
+        }
+        let diff = lhs
+            .iter()
+            .zip(rhs)
+            .fold(0, |diff, (lhs, rhs)| diff | (lhs.to_bits() ^ rhs.to_bits()));
+        hint::black_box(diff) == 0
+    }
+}
+
+impl<T, S, D> Eq for SecretVect<T, S, D>
+where
+    T: SecretElement,
+    S: Size,
+    D: SecretStorage<T>,
+{
+}
+
+mod sealed {
+    use crate::storage::{Array, InlineLen};
+
+    pub trait Sealed {}
+
+    impl<T, N> Sealed for Array<T, N> where N: InlineLen {}
+
+    #[cfg(feature = "alloc")]
+    impl<T> Sealed for crate::common::Vec<T> {}
+}
@@ -1,6 +1,17 @@
 //! Defines the size type which value is static or dynamic
+//!
+//! Static sizes are typenum binary integers. Every type operator here recurses over
+//! the bits of its operands at most, so the cost of type checking grows with `log N`
+//! rather than `N`, and large lengths such as `U65536` stay cheap to check. The
+//! measurements and regression tests are kept in the `regression` crate of the repository.
 
-use crate::common::*;
+use crate::{
+    common::*,
+    storage::{Array, InlineLen, Storage},
+};
+
+#[cfg(not(feature = "alloc"))]
+use crate::storage::NoStorage;
 
 // IntoSize
 
@@ -17,7 +28,7 @@ impl IntoSize for usize {
     type Output = Dyn;
 
     fn into_size(self) -> Self::Output {
-        Dyn(self)
+        Dyn::new(self)
     }
 }
 
@@ -39,8 +50,7 @@ impl IntoSize for UTerm {
 
 impl<U, B> IntoSize for UInt<U, B>
 where
-    U: Unsigned,
-    B: Bit,
+    Self: InlineLen,
 {
     type Output = Self;
 
@@ -53,16 +63,29 @@ where
 
 /// Marks the size type.
 pub trait Size {
+    /// The storage of a vector with this size.
+    ///
+    /// It is a `Vec` for [Dyn], and an inline [Array] for static sizes.
+    /// Without the `alloc` feature, [Dyn] has the placeholder `NoStorage` instead.
+    type DefaultStorage<T>: Storage<T>;
+
     fn to_usize(&self) -> usize;
 }
 
 impl Size for Dyn {
+    #[cfg(feature = "alloc")]
+    type DefaultStorage<T> = Vec<T>;
+    #[cfg(not(feature = "alloc"))]
+    type DefaultStorage<T> = NoStorage<T>;
+
     fn to_usize(&self) -> usize {
         self.0
     }
 }
 
 impl Size for UTerm {
+    type DefaultStorage<T> = Array<T, Self>;
+
     fn to_usize(&self) -> usize {
         Self::USIZE
     }
@@ -70,9 +93,10 @@ impl Size for UTerm {
 
 impl<U, B> Size for UInt<U, B>
 where
-    U: Unsigned,
-    B: Bit,
+    Self: InlineLen,
 {
+    type DefaultStorage<T> = Array<T, Self>;
+
     fn to_usize(&self) -> usize {
         Self::USIZE
     }
@@ -81,8 +105,174 @@ where
 // Dyn
 
 /// The dynamic size type. It is used when the size is not known in compile time.
+///
+/// Adding or multiplying sizes panics on overflow, even in release builds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct Dyn(usize);
 
+impl Dyn {
+    /// Creates a dynamic size from a runtime value.
+    pub const fn new(size: usize) -> Self {
+        Self(size)
+    }
+
+    /// Gets the runtime value of the size.
+    pub const fn get(&self) -> usize {
+        self.0
+    }
+
+    /// Subtracts a size, returning `None` if the result would be negative.
+    pub fn checked_sub<S>(self, rhs: S) -> Option<Dyn>
+    where
+        S: Size,
+    {
+        self.0.checked_sub(rhs.to_usize()).map(Dyn)
+    }
+
+    fn checked_op(
+        lhs: usize,
+        rhs: usize,
+        op: fn(usize, usize) -> Option<usize>,
+        name: &str,
+    ) -> Self {
+        match op(lhs, rhs) {
+            Some(size) => Dyn(size),
+            None => panic!("size {} overflows ({} and {})", name, lhs, rhs),
+        }
+    }
+}
+
+impl From<usize> for Dyn {
+    fn from(size: usize) -> Self {
+        Self(size)
+    }
+}
+
+impl From<Dyn> for usize {
+    fn from(size: Dyn) -> Self {
+        size.0
+    }
+}
+
+impl Display for Dyn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+macro_rules! impl_dyn_ops {
+    ($trait:ident, $method:ident, $checked:ident, $name:literal) => {
+        impl $trait<Dyn> for Dyn {
+            type Output = Dyn;
+
+            fn $method(self, rhs: Dyn) -> Self::Output {
+                Dyn::checked_op(self.0, rhs.0, usize::$checked, $name)
+            }
+        }
+
+        impl $trait<UTerm> for Dyn {
+            type Output = Dyn;
+
+            fn $method(self, rhs: UTerm) -> Self::Output {
+                Dyn::checked_op(self.0, rhs.to_usize(), usize::$checked, $name)
+            }
+        }
+
+        impl<U, B> $trait<UInt<U, B>> for Dyn
+        where
+            UInt<U, B>: Size,
+        {
+            type Output = Dyn;
+
+            fn $method(self, rhs: UInt<U, B>) -> Self::Output {
+                Dyn::checked_op(self.0, rhs.to_usize(), usize::$checked, $name)
+            }
+        }
+
+        impl $trait<Dyn> for UTerm {
+            type Output = Dyn;
+
+            fn $method(self, rhs: Dyn) -> Self::Output {
+                Dyn::checked_op(self.to_usize(), rhs.0, usize::$checked, $name)
+            }
+        }
+
+        impl<U, B> $trait<Dyn> for UInt<U, B>
+        where
+            UInt<U, B>: Size,
+        {
+            type Output = Dyn;
+
+            fn $method(self, rhs: Dyn) -> Self::Output {
+                Dyn::checked_op(self.to_usize(), rhs.0, usize::$checked, $name)
+            }
+        }
+    };
+}
+
+impl_dyn_ops!(Add, add, checked_add, "addition");
+impl_dyn_ops!(Mul, mul, checked_mul, "multiplication");
+
+impl PartialEq<UTerm> for Dyn {
+    fn eq(&self, other: &UTerm) -> bool {
+        self.0 == other.to_usize()
+    }
+}
+
+impl<U, B> PartialEq<UInt<U, B>> for Dyn
+where
+    UInt<U, B>: Size,
+{
+    fn eq(&self, other: &UInt<U, B>) -> bool {
+        self.0 == other.to_usize()
+    }
+}
+
+impl PartialEq<Dyn> for UTerm {
+    fn eq(&self, other: &Dyn) -> bool {
+        self.to_usize() == other.0
+    }
+}
+
+impl<U, B> PartialEq<Dyn> for UInt<U, B>
+where
+    UInt<U, B>: Size,
+{
+    fn eq(&self, other: &Dyn) -> bool {
+        self.to_usize() == other.0
+    }
+}
+
+impl PartialOrd<UTerm> for Dyn {
+    fn partial_cmp(&self, other: &UTerm) -> Option<Ordering> {
+        self.0.partial_cmp(&other.to_usize())
+    }
+}
+
+impl<U, B> PartialOrd<UInt<U, B>> for Dyn
+where
+    UInt<U, B>: Size,
+{
+    fn partial_cmp(&self, other: &UInt<U, B>) -> Option<Ordering> {
+        self.0.partial_cmp(&other.to_usize())
+    }
+}
+
+impl PartialOrd<Dyn> for UTerm {
+    fn partial_cmp(&self, other: &Dyn) -> Option<Ordering> {
+        self.to_usize().partial_cmp(&other.0)
+    }
+}
+
+impl<U, B> PartialOrd<Dyn> for UInt<U, B>
+where
+    UInt<U, B>: Size,
+{
+    fn partial_cmp(&self, other: &Dyn) -> Option<Ordering> {
+        self.to_usize().partial_cmp(&other.0)
+    }
+}
+
 // ops
 
 typ! {
@@ -106,7 +296,7 @@ typ! {
 
     pub fn DecreaseOne<size>(size: Size) -> Size {
         match size {
-            Dyn => Option::<Dyn>,
+            Dyn => Dyn,
             #[generics(uint: Unsigned, bit: Bit)]
             UInt::<uint, bit> => UInt::<uint, bit> - 1u
         }
@@ -120,11 +310,19 @@ typ! {
         }
     }
 
+    pub fn SizeMul<lhs, rhs>(lhs: Size, rhs: Size) -> Size {
+        if IsDyn(lhs) || IsDyn(rhs) {
+            Dyn
+        } else {
+            lhs * rhs
+        }
+    }
+
     pub fn CheckIndex<length, index>(length: Size, index: Size) {
         if IsDyn(length) || IsDyn(index) {
             Option::<()>
         } else {
-            match index >= 0u && index < length {
+            match index < length {
                 B1 => (),
             }
         }
@@ -134,7 +332,7 @@ typ! {
         if IsDyn(length) || IsDyn(index) {
             Option::<()>
         } else {
-            match index >= 0u && index <= length {
+            match index <= length {
                 B1 => (),
             }
         }
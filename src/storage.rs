@@ -0,0 +1,1582 @@
+//! Backing storages of the type-safe vector.
+//!
+//! The storage is the third parameter of [Vect](crate::Vect). It defaults to
+//! [Size::DefaultStorage]: a vector with dynamic length keeps its
+//! elements in a `Vec`, while a vector with static length keeps them inline in an
+//! [Array], so that `Vect<T, U4>` lives on the stack and is [Copy] whenever
+//! `T` is. Other storages, such as `Box<[T]>`, `VecDeque`,
+//! `Rc<[T]>` and the structurally shared `Persistent`, can be picked
+//! explicitly.
+//!
+//! Without the `alloc` feature, only the inline storage is available, and vectors with
+//! dynamic length need a storage given by the user.
+//!
+//! ```rust
+//! use std::collections::VecDeque;
+//! use type_vec::Vect;
+//! use typenum::consts::*;
+//!
+//! let vec = Vect::<usize, U0, VecDeque<usize>>::new().push(1).push(2);
+//! let vec: Vect<usize, U3, VecDeque<usize>> = vec.insert(U0::new(), 0);
+//!
+//! // change the storage while keeping the length
+//! let vec: Vect<usize, U3> = vec.into_storage();
+//! assert_eq!(vec, [0, 1, 2]);
+//! ```
+
+use crate::{common::*, size::Size};
+
+// Storage
+
+/// The container that holds the elements of a vector.
+///
+/// The length-changing operations take the storage by value and return the storage
+/// for the new size, which is given by [Resized](Storage::Resized).
+///
+/// # Safety
+/// The vector relies on the storage to report its elements faithfully. The length,
+/// element accesses and iterators must agree on the stored elements, and the
+/// length-changing operations must add or remove exactly one element.
+pub unsafe trait Storage<T>: Sized {
+    /// The storage type that holds a vector of size `S`.
+    type Resized<S: Size>: Storage<T>;
+
+    /// The iterator over the elements.
+    type Iter<'a>: Iterator<Item = &'a T> + DoubleEndedIterator + ExactSizeIterator + FusedIterator
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// The mutable iterator over the elements.
+    type IterMut<'a>: Iterator<Item = &'a mut T>
+        + DoubleEndedIterator
+        + ExactSizeIterator
+        + FusedIterator
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// The owning iterator over the elements.
+    type IntoIter: Iterator<Item = T> + DoubleEndedIterator + ExactSizeIterator + FusedIterator;
+
+    /// Builds the storage from an iterator.
+    ///
+    /// Storages with fixed length panic if the iterator does not yield exactly as many items.
+    fn from_exact_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>;
+
+    /// Builds the storage from a [Vec].
+    #[cfg(feature = "alloc")]
+    fn from_vec(vec: Vec<T>) -> Self {
+        Self::from_exact_iter(vec)
+    }
+
+    /// Converts into an owning iterator.
+    fn into_iter(self) -> Self::IntoIter;
+
+    /// Converts into a [Vec].
+    #[cfg(feature = "alloc")]
+    fn into_vec(self) -> Vec<T> {
+        self.into_iter().collect()
+    }
+
+    /// Converts into the heap buffer of the elements, if the storage owns one that
+    /// converts into a [Vec] without copying. Inline and shared storages return `None`.
+    #[cfg(feature = "alloc")]
+    fn into_buffer(self) -> Option<Vec<T>> {
+        None
+    }
+
+    /// Gets the number of elements.
+    fn len(&self) -> usize;
+
+    /// Returns true if the storage contains no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the number of elements the storage can hold without reallocating.
+    fn capacity(&self) -> usize {
+        self.len()
+    }
+
+    /// Returns a reference to the element at `index`.
+    fn get(&self, index: usize) -> Option<&T>;
+
+    /// Returns a mutable reference to the element at `index`.
+    fn get_mut(&mut self, index: usize) -> Option<&mut T>;
+
+    /// Returns a reference to the element at `index` without bound check.
+    ///
+    /// # Safety
+    /// The index must be less than the length.
+    unsafe fn get_unchecked(&self, index: usize) -> &T {
+        self.get(index).unwrap_unchecked()
+    }
+
+    /// Returns a mutable reference to the element at `index` without bound check.
+    ///
+    /// # Safety
+    /// The index must be less than the length.
+    unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+        self.get_mut(index).unwrap_unchecked()
+    }
+
+    /// Returns an iterator over the elements.
+    fn iter(&self) -> Self::Iter<'_>;
+
+    /// Returns an iterator that allows modifying each element.
+    fn iter_mut(&mut self) -> Self::IterMut<'_>;
+
+    /// Feeds the elements into a hasher.
+    ///
+    /// Contiguous storages hash like a slice, so that hashing agrees with
+    /// [Borrow<\[T\]>](Borrow).
+    fn hash_elements<H>(&self, state: &mut H)
+    where
+        T: Hash,
+        H: Hasher,
+    {
+        state.write_usize(self.len());
+        self.iter().for_each(|elem| elem.hash(state));
+    }
+
+    /// Appends an element to the back.
+    fn push<S>(self, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        Self::Resized::<S>::from_exact_iter(self.into_iter().chain(iter::once(item)))
+    }
+
+    /// Removes the last element, or gives back the storage if it is empty.
+    fn pop<S>(self) -> Result<(Self::Resized<S>, T), Self>
+    where
+        S: Size,
+    {
+        if self.is_empty() {
+            return Err(self);
+        }
+        let mut iter = self.into_iter();
+        let item = iter.next_back().unwrap();
+        Ok((Self::Resized::<S>::from_exact_iter(iter), item))
+    }
+
+    /// Inserts an element at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    fn insert<S>(self, index: usize, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+        let mut iter = self.into_iter();
+        let mut item = Some(item);
+        let mut position = 0;
+        Self::Resized::<S>::from_exact_iter(iter::from_fn(|| {
+            let next = if position == index {
+                item.take()
+            } else {
+                iter.next()
+            };
+            position += 1;
+            next
+        }))
+    }
+
+    /// Removes the element at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index >= len`.
+    fn remove<S>(self, index: usize) -> (Self::Resized<S>, T)
+    where
+        S: Size,
+    {
+        let len = self.len();
+        assert!(
+            index < len,
+            "removal index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+        let mut iter = self.into_iter();
+        let mut removed = None;
+        let mut position = 0;
+        let data = Self::Resized::<S>::from_exact_iter(iter::from_fn(|| {
+            if position == index {
+                removed = iter.next();
+            }
+            position += 1;
+            iter.next()
+        }));
+        // the last element is not visited when it is removed
+        let removed = removed.or_else(|| iter.next());
+        (data, removed.unwrap())
+    }
+}
+
+/// The storage that keeps its elements in a single slice.
+///
+/// # Safety
+/// The slices must contain exactly the elements reported by [Storage].
+pub unsafe trait ContiguousStorage<T>: Storage<T> {
+    /// Returns the elements as a slice.
+    fn as_slice(&self) -> &[T];
+
+    /// Returns the elements as a mutable slice.
+    fn as_mut_slice(&mut self) -> &mut [T];
+}
+
+/// The storage that can hold a vector of size `S`.
+///
+/// Storages with dynamic length hold vectors of any size, while an [Array<T, N>](Array)
+/// only holds vectors of size `N`. The constructors of [Vect](crate::Vect) require it,
+/// so that an inline storage cannot be picked for a vector of another size.
+///
+/// ```compile_fail,E0277
+/// use type_vec::{Array, Vect};
+/// use typenum::consts::*;
+///
+/// let vec = Vect::<u64, U8, Array<u64, U1>>::from_fn(|index| index as u64);
+/// ```
+pub trait StorageFor<T, S>: Storage<T>
+where
+    S: Size,
+{
+}
+
+/// Implements the element accesses of [Storage] by the slice of a
+/// [ContiguousStorage].
+macro_rules! impl_slice_access {
+    () => {
+        type Iter<'a>
+            = slice::Iter<'a, T>
+        where
+            Self: 'a,
+            T: 'a;
+
+        type IterMut<'a>
+            = slice::IterMut<'a, T>
+        where
+            Self: 'a,
+            T: 'a;
+
+        fn len(&self) -> usize {
+            ContiguousStorage::as_slice(self).len()
+        }
+
+        fn get(&self, index: usize) -> Option<&T> {
+            ContiguousStorage::as_slice(self).get(index)
+        }
+
+        fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+            ContiguousStorage::as_mut_slice(self).get_mut(index)
+        }
+
+        unsafe fn get_unchecked(&self, index: usize) -> &T {
+            ContiguousStorage::as_slice(self).get_unchecked(index)
+        }
+
+        unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut T {
+            ContiguousStorage::as_mut_slice(self).get_unchecked_mut(index)
+        }
+
+        fn iter(&self) -> Self::Iter<'_> {
+            ContiguousStorage::as_slice(self).iter()
+        }
+
+        fn iter_mut(&mut self) -> Self::IterMut<'_> {
+            ContiguousStorage::as_mut_slice(self).iter_mut()
+        }
+
+        fn hash_elements<H>(&self, state: &mut H)
+        where
+            T: Hash,
+            H: Hasher,
+        {
+            ContiguousStorage::as_slice(self).hash(state)
+        }
+    };
+}
+
+// Vec
+
+#[cfg(feature = "alloc")]
+unsafe impl<T> Storage<T> for Vec<T> {
+    type Resized<S: Size> = Vec<T>;
+    type IntoIter = vec::IntoIter<T>;
+
+    impl_slice_access!();
+
+    fn from_exact_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().collect()
+    }
+
+    fn from_vec(vec: Vec<T>) -> Self {
+        vec
+    }
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self)
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self
+    }
+
+    fn into_buffer(self) -> Option<Vec<T>> {
+        Some(Storage::into_vec(self))
+    }
+
+    fn capacity(&self) -> usize {
+        Vec::capacity(self)
+    }
+
+    fn push<S>(mut self, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        Vec::push(&mut self, item);
+        self
+    }
+
+    fn pop<S>(mut self) -> Result<(Self::Resized<S>, T), Self>
+    where
+        S: Size,
+    {
+        match Vec::pop(&mut self) {
+            Some(item) => Ok((self, item)),
+            None => Err(self),
+        }
+    }
+
+    fn insert<S>(mut self, index: usize, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        Vec::insert(&mut self, index, item);
+        self
+    }
+
+    fn remove<S>(mut self, index: usize) -> (Self::Resized<S>, T)
+    where
+        S: Size,
+    {
+        let item = Vec::remove(&mut self, index);
+        (self, item)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S> StorageFor<T, S> for Vec<T> where S: Size {}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T> ContiguousStorage<T> for Vec<T> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+// Box<[T]>
+
+/// The boxed slice is reallocated on every length change.
+#[cfg(feature = "alloc")]
+unsafe impl<T> Storage<T> for Box<[T]> {
+    type Resized<S: Size> = Box<[T]>;
+    type IntoIter = vec::IntoIter<T>;
+
+    impl_slice_access!();
+
+    fn from_exact_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().collect()
+    }
+
+    fn from_vec(vec: Vec<T>) -> Self {
+        vec.into_boxed_slice()
+    }
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(Storage::into_vec(self))
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        <[T]>::into_vec(self)
+    }
+
+    fn into_buffer(self) -> Option<Vec<T>> {
+        Some(Storage::into_vec(self))
+    }
+
+    fn push<S>(self, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        Storage::push::<S>(<[T]>::into_vec(self), item).into_boxed_slice()
+    }
+
+    fn pop<S>(self) -> Result<(Self::Resized<S>, T), Self>
+    where
+        S: Size,
+    {
+        match Storage::pop::<S>(<[T]>::into_vec(self)) {
+            Ok((vec, item)) => Ok((vec.into_boxed_slice(), item)),
+            Err(vec) => Err(vec.into_boxed_slice()),
+        }
+    }
+
+    fn insert<S>(self, index: usize, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        Storage::insert::<S>(<[T]>::into_vec(self), index, item).into_boxed_slice()
+    }
+
+    fn remove<S>(self, index: usize) -> (Self::Resized<S>, T)
+    where
+        S: Size,
+    {
+        let (vec, item) = Storage::remove::<S>(<[T]>::into_vec(self), index);
+        (vec.into_boxed_slice(), item)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S> StorageFor<T, S> for Box<[T]> where S: Size {}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T> ContiguousStorage<T> for Box<[T]> {
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+// VecDeque
+
+#[cfg(feature = "alloc")]
+unsafe impl<T> Storage<T> for VecDeque<T> {
+    type Resized<S: Size> = VecDeque<T>;
+    type IntoIter = vec_deque::IntoIter<T>;
+
+    type Iter<'a>
+        = vec_deque::Iter<'a, T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    type IterMut<'a>
+        = vec_deque::IterMut<'a, T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn from_exact_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().collect()
+    }
+
+    fn from_vec(vec: Vec<T>) -> Self {
+        vec.into()
+    }
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self)
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self.into()
+    }
+
+    fn into_buffer(self) -> Option<Vec<T>> {
+        Some(Storage::into_vec(self))
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn capacity(&self) -> usize {
+        VecDeque::capacity(self)
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        VecDeque::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        VecDeque::get_mut(self, index)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        VecDeque::iter(self)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        VecDeque::iter_mut(self)
+    }
+
+    fn push<S>(mut self, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        self.push_back(item);
+        self
+    }
+
+    fn pop<S>(mut self) -> Result<(Self::Resized<S>, T), Self>
+    where
+        S: Size,
+    {
+        match self.pop_back() {
+            Some(item) => Ok((self, item)),
+            None => Err(self),
+        }
+    }
+
+    fn insert<S>(mut self, index: usize, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        VecDeque::insert(&mut self, index, item);
+        self
+    }
+
+    fn remove<S>(mut self, index: usize) -> (Self::Resized<S>, T)
+    where
+        S: Size,
+    {
+        let len = VecDeque::len(&self);
+        match VecDeque::remove(&mut self, index) {
+            Some(item) => (self, item),
+            None => panic!("removal index (is {}) should be < len (is {})", index, len),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S> StorageFor<T, S> for VecDeque<T> where S: Size {}
+
+// Rc<[T]>
+
+/// The shared slice is cloned on mutation and on length change if it is shared.
+#[cfg(feature = "alloc")]
+unsafe impl<T> Storage<T> for Rc<[T]>
+where
+    T: Clone,
+{
+    type Resized<S: Size> = Rc<[T]>;
+    type IntoIter = vec::IntoIter<T>;
+
+    impl_slice_access!();
+
+    fn from_exact_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        iter.into_iter().collect()
+    }
+
+    fn from_vec(vec: Vec<T>) -> Self {
+        vec.into()
+    }
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(Storage::into_vec(self))
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        self.to_vec()
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T> ContiguousStorage<T> for Rc<[T]>
+where
+    T: Clone,
+{
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        if Rc::get_mut(self).is_none() {
+            *self = self.iter().cloned().collect();
+        }
+        Rc::get_mut(self).unwrap()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S> StorageFor<T, S> for Rc<[T]>
+where
+    T: Clone,
+    S: Size,
+{
+}
+
+// Persistent
+
+/// The maximum number of children or elements of each node of [Persistent].
+#[cfg(feature = "alloc")]
+const PERSISTENT_WIDTH: usize = 32;
+
+/// The minimum number of children or elements of each node of [Persistent], except
+/// the last child of each branch.
+#[cfg(feature = "alloc")]
+const PERSISTENT_MIN: usize = PERSISTENT_WIDTH / 2;
+
+/// The persistent storage that shares structure between versions.
+///
+/// The elements are kept in a balanced tree of [Rc]-shared chunks, each of which holds
+/// up to 32 elements. Like the relaxed nodes of an RRB tree, the branches record the
+/// sizes of their children, so that chunks need not be full. Cloning the storage is `O(1)`, and a
+/// length-changing operation on a clone copies only the path to the index it modifies,
+/// that is `O(log n)` for `push`, `pop`, `insert` and `remove`. The other chunks stay
+/// shared with the old version.
+/// Mutation through [get_mut](Storage::get_mut) or [iter_mut](Storage::iter_mut) also
+/// copies the shared nodes on write, so the other versions are never affected.
+///
+/// ```rust
+/// use type_vec::{storage::Persistent, Vect};
+/// use typenum::consts::*;
+///
+/// let v2 = Vect::<usize, U0, Persistent<usize>>::new().push(1).push(2);
+///
+/// // keep the old version and derive new ones from it
+/// let v3 = v2.clone().push(3);
+/// let (v1, _) = v2.clone().pop();
+/// let v3b = v2.clone().insert(U0::new(), 0);
+///
+/// assert_eq!(v1, [1]);
+/// assert_eq!(v2, [1, 2]);
+/// assert_eq!(v3, [1, 2, 3]);
+/// assert_eq!(v3b, [0, 1, 2]);
+/// ```
+#[cfg(feature = "alloc")]
+pub struct Persistent<T> {
+    len: usize,
+    root: Rc<TrieNode<T>>,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+enum TrieNode<T> {
+    Branch {
+        /// The cumulative numbers of elements of the children.
+        sizes: Vec<usize>,
+        children: Vec<Rc<TrieNode<T>>>,
+    },
+    Leaf(Vec<T>),
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TrieNode<T> {
+    fn branch(children: Vec<Rc<Self>>) -> Self {
+        let mut node = Self::Branch {
+            sizes: vec![],
+            children,
+        };
+        node.update_sizes(0);
+        node
+    }
+
+    /// Gets the number of elements under the node.
+    fn len(&self) -> usize {
+        match self {
+            Self::Branch { sizes, .. } => sizes.last().copied().unwrap_or(0),
+            Self::Leaf(items) => items.len(),
+        }
+    }
+
+    /// Gets the number of children or elements of the node.
+    fn width(&self) -> usize {
+        match self {
+            Self::Branch { children, .. } => children.len(),
+            Self::Leaf(items) => items.len(),
+        }
+    }
+
+    /// Recomputes the cumulative sizes from the child at `from` on.
+    fn update_sizes(&mut self, from: usize) {
+        if let Self::Branch { sizes, children } = self {
+            sizes.truncate(from);
+            let mut total = sizes.last().copied().unwrap_or(0);
+            sizes.extend(children[from..].iter().map(|child| {
+                total += child.len();
+                total
+            }));
+        }
+    }
+
+    /// Finds the child that holds `index`, and the index within the child.
+    /// The past-the-end index is located in the last child.
+    fn locate(sizes: &[usize], index: usize) -> (usize, usize) {
+        let slot = sizes
+            .partition_point(|&size| size <= index)
+            .min(sizes.len() - 1);
+        let offset = match slot {
+            0 => index,
+            _ => index - sizes[slot - 1],
+        };
+        (slot, offset)
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        let mut node = self;
+        let mut index = index;
+        loop {
+            match node {
+                Self::Branch { sizes, children } => {
+                    let (slot, offset) = Self::locate(sizes, index);
+                    node = &children[slot];
+                    index = offset;
+                }
+                Self::Leaf(items) => return items.get(index),
+            }
+        }
+    }
+
+    fn leaves<'a>(&'a self, leaves: &mut Vec<&'a [T]>) {
+        match self {
+            Self::Branch { children, .. } => children.iter().for_each(|child| child.leaves(leaves)),
+            Self::Leaf(items) => leaves.push(items),
+        }
+    }
+
+    /// Splits the entries of an overflowing node into a new right sibling.
+    ///
+    /// Appending to a full node moves only the last entry, so that pushes leave the
+    /// chunks full. Otherwise the entries are split in halves.
+    fn split(&mut self, appended: bool) -> Self {
+        let at = if appended {
+            PERSISTENT_WIDTH
+        } else {
+            self.width() / 2
+        };
+        match self {
+            Self::Branch { children, .. } => {
+                let right = Self::branch(children.split_off(at));
+                self.update_sizes(at);
+                right
+            }
+            Self::Leaf(items) => Self::Leaf(items.split_off(at)),
+        }
+    }
+
+    /// Moves entries from the front of `right` to the back of `left`, until `left`
+    /// holds `count` entries.
+    fn shift_left(left: &mut Self, right: &mut Self, count: usize) {
+        let moved = count - left.width();
+        match (&mut *left, &mut *right) {
+            (Self::Branch { children: lhs, .. }, Self::Branch { children: rhs, .. }) => {
+                lhs.extend(rhs.drain(..moved));
+            }
+            (Self::Leaf(lhs), Self::Leaf(rhs)) => lhs.extend(rhs.drain(..moved)),
+            _ => unreachable!("siblings are at the same depth"),
+        }
+        left.update_sizes(count - moved);
+        right.update_sizes(0);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TrieNode<T>
+where
+    T: Clone,
+{
+    fn get_mut(node: &mut Rc<Self>, index: usize) -> Option<&mut T> {
+        match Rc::make_mut(node) {
+            Self::Branch { sizes, children } => {
+                let (slot, offset) = Self::locate(sizes, index);
+                Self::get_mut(&mut children[slot], offset)
+            }
+            Self::Leaf(items) => items.get_mut(index),
+        }
+    }
+
+    /// Inserts an element, and returns the new right sibling if the node overflows.
+    fn insert(node: &mut Rc<Self>, index: usize, item: T) -> Option<Self> {
+        let appended = index == node.len();
+        let node = Rc::make_mut(node);
+        match node {
+            Self::Branch { sizes, children } => {
+                let (slot, offset) = Self::locate(sizes, index);
+                if let Some(right) = Self::insert(&mut children[slot], offset, item) {
+                    children.insert(slot + 1, Rc::new(right));
+                }
+                node.update_sizes(slot);
+            }
+            Self::Leaf(items) => items.insert(index, item),
+        }
+        (node.width() > PERSISTENT_WIDTH).then(|| node.split(appended))
+    }
+
+    /// Removes an element, and rebalances the children that become too small.
+    ///
+    /// The last child of a branch may be smaller, so that popping never rebalances.
+    fn remove(node: &mut Rc<Self>, index: usize) -> T {
+        let node = Rc::make_mut(node);
+        match node {
+            Self::Branch { sizes, children } => {
+                let (slot, offset) = Self::locate(sizes, index);
+                let item = Self::remove(&mut children[slot], offset);
+                if children[slot].width() == 0 {
+                    children.remove(slot);
+                } else if slot + 1 < children.len() && children[slot].width() < PERSISTENT_MIN {
+                    let (lhs, rhs) = children.split_at_mut(slot + 1);
+                    let (left, right) = (Rc::make_mut(&mut lhs[slot]), Rc::make_mut(&mut rhs[0]));
+                    let total = left.width() + right.width();
+                    if total <= PERSISTENT_WIDTH {
+                        Self::shift_left(left, right, total);
+                        children.remove(slot + 1);
+                    } else {
+                        Self::shift_left(left, right, total / 2);
+                    }
+                }
+                node.update_sizes(slot);
+                item
+            }
+            Self::Leaf(items) => items.remove(index),
+        }
+    }
+
+    fn leaves_mut<'a>(node: &'a mut Rc<Self>, leaves: &mut Vec<&'a mut [T]>) {
+        match Rc::make_mut(node) {
+            Self::Branch { children, .. } => children
+                .iter_mut()
+                .for_each(|child| Self::leaves_mut(child, leaves)),
+            Self::Leaf(items) => leaves.push(items),
+        }
+    }
+
+    /// Moves the elements out if the node is not shared, or clones them otherwise.
+    fn drain(node: Rc<Self>, items: &mut Vec<T>) {
+        match Rc::try_unwrap(node) {
+            Ok(Self::Branch { children, .. }) => {
+                IntoIterator::into_iter(children).for_each(|child| Self::drain(child, items))
+            }
+            Ok(Self::Leaf(leaf)) => items.extend(leaf),
+            Err(node) => match &*node {
+                Self::Branch { children, .. } => children
+                    .iter()
+                    .for_each(|child| Self::drain(child.clone(), items)),
+                Self::Leaf(leaf) => items.extend_from_slice(leaf),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Persistent<T>
+where
+    T: Clone,
+{
+    /// Returns true if both storages share the same root, that is, they are versions
+    /// that have not been modified since one was cloned from the other.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.root, &other.root)
+    }
+
+    fn insert_mut(&mut self, index: usize, item: T) {
+        if let Some(right) = TrieNode::insert(&mut self.root, index, item) {
+            let left = mem::replace(&mut self.root, Rc::new(TrieNode::Leaf(vec![])));
+            self.root = Rc::new(TrieNode::branch(vec![left, Rc::new(right)]));
+        }
+        self.len += 1;
+    }
+
+    fn remove_mut(&mut self, index: usize) -> T {
+        let item = TrieNode::remove(&mut self.root, index);
+        self.len -= 1;
+        // shrink the root while it has a single child
+        loop {
+            let child = match &*self.root {
+                TrieNode::Branch { children, .. } if children.len() <= 1 => {
+                    children.first().cloned()
+                }
+                _ => break,
+            };
+            self.root = child.unwrap_or_else(|| Rc::new(TrieNode::Leaf(vec![])));
+        }
+        item
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Clone for Persistent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            len: self.len,
+            root: self.root.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Debug for Persistent<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut leaves = vec![];
+        self.root.leaves(&mut leaves);
+        f.debug_list()
+            .entries(IntoIterator::into_iter(leaves).flatten())
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T> Storage<T> for Persistent<T>
+where
+    T: Clone,
+{
+    type Resized<S: Size> = Persistent<T>;
+    type IntoIter = vec::IntoIter<T>;
+
+    type Iter<'a>
+        = ChunksIter<&'a [T]>
+    where
+        Self: 'a,
+        T: 'a;
+
+    type IterMut<'a>
+        = ChunksIter<&'a mut [T]>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn from_exact_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut storage = Self {
+            len: 0,
+            root: Rc::new(TrieNode::Leaf(vec![])),
+        };
+        iter.into_iter()
+            .for_each(|item| storage.insert_mut(storage.len, item));
+        storage
+    }
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(Storage::into_vec(self))
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        let mut items = Vec::with_capacity(self.len);
+        TrieNode::drain(self.root, &mut items);
+        items
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        self.root.get(index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+        TrieNode::get_mut(&mut self.root, index)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        let mut leaves = vec![];
+        self.root.leaves(&mut leaves);
+        ChunksIter::new(leaves, self.len)
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let mut leaves = vec![];
+        TrieNode::leaves_mut(&mut self.root, &mut leaves);
+        ChunksIter::new(leaves, self.len)
+    }
+
+    fn push<S>(mut self, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        self.insert_mut(self.len, item);
+        self
+    }
+
+    fn pop<S>(mut self) -> Result<(Self::Resized<S>, T), Self>
+    where
+        S: Size,
+    {
+        match self.len.checked_sub(1) {
+            Some(index) => {
+                let item = self.remove_mut(index);
+                Ok((self, item))
+            }
+            None => Err(self),
+        }
+    }
+
+    fn insert<S>(mut self, index: usize, item: T) -> Self::Resized<S>
+    where
+        S: Size,
+    {
+        assert!(
+            index <= self.len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            self.len
+        );
+        self.insert_mut(index, item);
+        self
+    }
+
+    fn remove<S>(mut self, index: usize) -> (Self::Resized<S>, T)
+    where
+        S: Size,
+    {
+        assert!(
+            index < self.len,
+            "removal index (is {}) should be < len (is {})",
+            index,
+            self.len
+        );
+        let item = self.remove_mut(index);
+        (self, item)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S> StorageFor<T, S> for Persistent<T>
+where
+    T: Clone,
+    S: Size,
+{
+}
+
+/// The iterator over the chunks of a [Persistent] storage.
+#[cfg(feature = "alloc")]
+pub struct ChunksIter<C>
+where
+    C: IntoIterator,
+{
+    chunks: vec::IntoIter<C>,
+    front: C::IntoIter,
+    back: C::IntoIter,
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<C> ChunksIter<C>
+where
+    C: IntoIterator,
+    C::IntoIter: Default,
+{
+    fn new(chunks: Vec<C>, len: usize) -> Self {
+        Self {
+            chunks: IntoIterator::into_iter(chunks),
+            front: Default::default(),
+            back: Default::default(),
+            len,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> Iterator for ChunksIter<C>
+where
+    C: IntoIterator,
+    C::IntoIter: DoubleEndedIterator,
+{
+    type Item = C::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.front.next() {
+                self.len -= 1;
+                return Some(item);
+            }
+            match self.chunks.next() {
+                Some(chunk) => self.front = chunk.into_iter(),
+                None => {
+                    let item = self.back.next()?;
+                    self.len -= 1;
+                    return Some(item);
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> DoubleEndedIterator for ChunksIter<C>
+where
+    C: IntoIterator,
+    C::IntoIter: DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.back.next_back() {
+                self.len -= 1;
+                return Some(item);
+            }
+            match self.chunks.next_back() {
+                Some(chunk) => self.back = chunk.into_iter(),
+                None => {
+                    let item = self.front.next_back()?;
+                    self.len -= 1;
+                    return Some(item);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<C> ExactSizeIterator for ChunksIter<C>
+where
+    C: IntoIterator,
+    C::IntoIter: DoubleEndedIterator,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<C> FusedIterator for ChunksIter<C>
+where
+    C: IntoIterator,
+    C::IntoIter: DoubleEndedIterator,
+{
+}
+
+// NoStorage
+
+/// The placeholder storage of vectors with dynamic length when `alloc` is disabled.
+///
+/// It has no values and does not implement [StorageFor], so that the
+/// constructors of a vector with dynamic length in the default storage, such as
+/// [Vect::new](crate::Vect::new), do not compile without a heap. Pick another storage
+/// explicitly instead.
+#[cfg(not(feature = "alloc"))]
+pub enum NoStorage<T> {
+    #[doc(hidden)]
+    Never(core::convert::Infallible, PhantomData<T>),
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<T> NoStorage<T> {
+    fn never(&self) -> ! {
+        match *self {
+            Self::Never(never, _) => match never {},
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+unsafe impl<T> Storage<T> for NoStorage<T> {
+    type Resized<S: Size> = NoStorage<T>;
+    type IntoIter = iter::Empty<T>;
+
+    type Iter<'a>
+        = slice::Iter<'a, T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    type IterMut<'a>
+        = slice::IterMut<'a, T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn from_exact_iter<I>(_iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        panic!("vectors with dynamic length require the `alloc` feature")
+    }
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.never()
+    }
+
+    fn len(&self) -> usize {
+        self.never()
+    }
+
+    fn get(&self, _index: usize) -> Option<&T> {
+        self.never()
+    }
+
+    fn get_mut(&mut self, _index: usize) -> Option<&mut T> {
+        self.never()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.never()
+    }
+
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        self.never()
+    }
+}
+
+// Array
+
+/// The inline array with type-level length `N`.
+///
+/// It has the same layout as `[T; N]`. The elements are laid out by the bits of `N`,
+/// so that the type is defined for any typenum integer without const generics.
+#[repr(transparent)]
+pub struct Array<T, N>
+where
+    N: InlineLen,
+{
+    data: N::Layout<T>,
+}
+
+impl<T, N> Array<T, N>
+where
+    N: InlineLen,
+{
+    /// Returns the elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        let ptr = if N::USIZE == 0 {
+            ptr::NonNull::dangling().as_ptr()
+        } else {
+            &self.data as *const N::Layout<T> as *const T
+        };
+        // SAFETY: the layout consists of exactly N consecutive elements.
+        unsafe { slice::from_raw_parts(ptr, N::USIZE) }
+    }
+
+    /// Returns the elements as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let ptr = if N::USIZE == 0 {
+            ptr::NonNull::dangling().as_ptr()
+        } else {
+            &mut self.data as *mut N::Layout<T> as *mut T
+        };
+        // SAFETY: the layout consists of exactly N consecutive elements.
+        unsafe { slice::from_raw_parts_mut(ptr, N::USIZE) }
+    }
+}
+
+impl<T, N> Clone for Array<T, N>
+where
+    T: Clone,
+    N: InlineLen,
+{
+    fn clone(&self) -> Self {
+        Self::from_exact_iter(self.as_slice().iter().cloned())
+    }
+}
+
+impl<T, N> Copy for Array<T, N>
+where
+    T: Copy,
+    N: InlineLen,
+    N::Layout<T>: Copy,
+{
+}
+
+impl<T, N> Debug for Array<T, N>
+where
+    T: Debug,
+    N: InlineLen,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+
+unsafe impl<T, N> Storage<T> for Array<T, N>
+where
+    N: InlineLen,
+{
+    type Resized<S: Size> = S::DefaultStorage<T>;
+    type IntoIter = ArrayIntoIter<T, N>;
+
+    impl_slice_access!();
+
+    fn from_exact_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut array = mem::MaybeUninit::<Self>::uninit();
+        let mut guard = InitGuard {
+            ptr: array.as_mut_ptr() as *mut T,
+            len: 0,
+        };
+        let mut iter = iter.into_iter();
+        for item in iter.by_ref().take(N::USIZE) {
+            // SAFETY: at most N elements are written.
+            unsafe { guard.ptr.add(guard.len).write(item) };
+            guard.len += 1;
+        }
+        assert_eq!(
+            guard.len,
+            N::USIZE,
+            "the iterator yields fewer items than the array length"
+        );
+        assert!(
+            iter.next().is_none(),
+            "the iterator yields more items than the array length"
+        );
+        mem::forget(guard);
+        // SAFETY: all N elements are initialized.
+        unsafe { array.assume_init() }
+    }
+
+    fn into_iter(self) -> Self::IntoIter {
+        ArrayIntoIter {
+            data: mem::MaybeUninit::new(self),
+            start: 0,
+            end: N::USIZE,
+        }
+    }
+}
+
+unsafe impl<T, N> ContiguousStorage<T> for Array<T, N>
+where
+    N: InlineLen,
+{
+    fn as_slice(&self) -> &[T] {
+        Array::as_slice(self)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        Array::as_mut_slice(self)
+    }
+}
+
+impl<T, N> StorageFor<T, N> for Array<T, N> where N: InlineLen + Size {}
+
+/// Drops the initialized prefix of a partially built array on panic.
+struct InitGuard<T> {
+    ptr: *mut T,
+    len: usize,
+}
+
+impl<T> Drop for InitGuard<T> {
+    fn drop(&mut self) {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr, self.len)) }
+    }
+}
+
+/// The owning iterator over an [Array].
+pub struct ArrayIntoIter<T, N>
+where
+    N: InlineLen,
+{
+    data: mem::MaybeUninit<Array<T, N>>,
+    start: usize,
+    end: usize,
+}
+
+impl<T, N> ArrayIntoIter<T, N>
+where
+    N: InlineLen,
+{
+    fn as_ptr(&self) -> *const T {
+        self.data.as_ptr() as *const T
+    }
+
+    /// Returns the remaining elements as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        if self.start == self.end {
+            return &[];
+        }
+        // SAFETY: the elements in `start..end` are initialized.
+        unsafe { slice::from_raw_parts(self.as_ptr().add(self.start), self.end - self.start) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.start == self.end {
+            return &mut [];
+        }
+        let ptr = self.data.as_mut_ptr() as *mut T;
+        // SAFETY: the elements in `start..end` are initialized.
+        unsafe { slice::from_raw_parts_mut(ptr.add(self.start), self.end - self.start) }
+    }
+}
+
+impl<T, N> AsRef<[T]> for ArrayIntoIter<T, N>
+where
+    N: InlineLen,
+{
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, N> Iterator for ArrayIntoIter<T, N>
+where
+    N: InlineLen,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        // SAFETY: the element at `start` is initialized and is moved out once.
+        let item = unsafe { self.as_ptr().add(self.start).read() };
+        self.start += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, N> DoubleEndedIterator for ArrayIntoIter<T, N>
+where
+    N: InlineLen,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        // SAFETY: the element at `end` is initialized and is moved out once.
+        Some(unsafe { self.as_ptr().add(self.end).read() })
+    }
+}
+
+impl<T, N> ExactSizeIterator for ArrayIntoIter<T, N> where N: InlineLen {}
+
+impl<T, N> FusedIterator for ArrayIntoIter<T, N> where N: InlineLen {}
+
+impl<T, N> Clone for ArrayIntoIter<T, N>
+where
+    T: Clone,
+    N: InlineLen,
+{
+    fn clone(&self) -> Self {
+        let mut data = mem::MaybeUninit::<Array<T, N>>::uninit();
+        let ptr = data.as_mut_ptr() as *mut T;
+        let mut end = self.start;
+        for item in self.as_slice() {
+            // SAFETY: the slot is within `start..self.end` and is uninitialized.
+            unsafe { ptr.add(end).write(item.clone()) };
+            end += 1;
+        }
+        Self {
+            data,
+            start: self.start,
+            end,
+        }
+    }
+}
+
+impl<T, N> Debug for ArrayIntoIter<T, N>
+where
+    T: Debug,
+    N: InlineLen,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArrayIntoIter")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+impl<T, N> Drop for ArrayIntoIter<T, N>
+where
+    N: InlineLen,
+{
+    fn drop(&mut self) {
+        // SAFETY: the remaining elements are initialized and dropped once.
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) }
+    }
+}
+
+// layout
+
+/// The static length that can be stored inline.
+///
+/// It is implemented for all typenum unsigned integers.
+pub trait InlineLen: Unsigned + sealed::Sealed {
+    #[doc(hidden)]
+    type Layout<T>;
+}
+
+/// The bit that decides whether an inline layout has an extra element.
+pub trait InlineBit: Bit + sealed::Sealed {
+    #[doc(hidden)]
+    type Extra<T>;
+}
+
+impl InlineLen for UTerm {
+    type Layout<T> = ();
+}
+
+impl<U, B> InlineLen for UInt<U, B>
+where
+    U: InlineLen,
+    B: InlineBit,
+{
+    type Layout<T> = Node<U::Layout<T>, B::Extra<T>>;
+}
+
+impl InlineBit for B0 {
+    type Extra<T> = ();
+}
+
+impl InlineBit for B1 {
+    type Extra<T> = T;
+}
+
+/// The layout of `2 * U + B` elements.
+#[doc(hidden)]
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Node<L, E> {
+    low: L,
+    high: L,
+    extra: E,
+}
+
+mod sealed {
+    use crate::common::*;
+
+    pub trait Sealed {}
+
+    impl Sealed for UTerm {}
+    impl<U, B> Sealed for UInt<U, B> {}
+    impl Sealed for B0 {}
+    impl Sealed for B1 {}
+}
@@ -1,34 +1,61 @@
 //! Defines the type-safe vector.
 
+#[cfg(feature = "alloc")]
+use crate::error::DynLengthError;
 use crate::{
     common::*,
+    error::LengthError,
     impls,
-    size::{Dyn, IntoSize, Size},
+    iter::{ExactIterItems, IntoIter},
+    size::{self, Dyn, IntoSize, Size},
+    storage::{ContiguousStorage, Storage, StorageFor},
 };
 
 /// The type-safe vector with type-level length.
-#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-pub struct Vect<T, S>
+///
+/// The elements are kept in the storage `D`, which defaults to
+/// [S::DefaultStorage](Size::DefaultStorage). A vector with static length keeps them
+/// inline without heap allocation, and is [Copy] if `T` is. See the
+/// [storage](crate::storage) module for other storages.
+///
+/// The vector is transparent over its storage. With the inline storage, it has the same
+/// layout as `[T; N]` and can be passed to C, see the [ffi](crate::ffi) module.
+#[repr(transparent)]
+pub struct Vect<T, S, D = <S as Size>::DefaultStorage<T>>
 where
     S: Size,
+    D: Storage<T>,
 {
-    pub(crate) data: Vec<T>,
-    pub(crate) _phantom: PhantomData<S>,
+    pub(crate) data: D,
+    pub(crate) _phantom: PhantomData<(T, S)>,
 }
 
-impl<T> Vect<T, U0> {
+impl<T, D> Vect<T, U0, D>
+where
+    D: Storage<T>,
+{
     /// Creates an empty vector with static length.
-    pub fn new() -> Self {
+    pub fn new() -> Self
+    where
+        D: StorageFor<T, U0>,
+    {
         Self {
-            data: vec![],
+            data: D::from_exact_iter(iter::empty()),
             _phantom: PhantomData,
         }
     }
 
     /// Creates an empty vector with static length and with specified capacity.
-    pub fn with_capacity(capacity: usize) -> Self {
+    ///
+    /// The capacity is ignored by storages that cannot grow in place, such as the
+    /// inline storage of static lengths.
+    #[cfg(feature = "alloc")]
+    pub fn with_capacity(capacity: usize) -> Self
+    where
+        D: StorageFor<T, U0>,
+    {
         Self {
-            data: Vec::with_capacity(capacity),
+            data: D::from_vec(Vec::with_capacity(capacity)),
             _phantom: PhantomData,
         }
     }
@@ -37,31 +64,56 @@ impl<T> Vect<T, U0> {
     pub fn len(&self) -> usize {
         U0::USIZE
     }
+
+    /// Returns true if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        true
+    }
 }
 
-impl<T> Vect<T, Dyn> {
+impl<T, D> Default for Vect<T, U0, D>
+where
+    D: StorageFor<T, U0>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, D> Vect<T, Dyn, D>
+where
+    D: Storage<T>,
+{
     /// Creates an empty vector with dynamic length.
-    pub fn new() -> Self {
+    pub fn new() -> Self
+    where
+        D: StorageFor<T, Dyn>,
+    {
         Self {
-            data: vec![],
+            data: D::from_exact_iter(iter::empty()),
             _phantom: PhantomData,
         }
     }
 
-    /// Creates a vector from [Vec](Vec).
-    pub fn from_vec(data: Vec<T>) -> Self {
+    /// Creates a vector from [Vec].
+    #[cfg(feature = "alloc")]
+    pub fn from_vec(data: Vec<T>) -> Self
+    where
+        D: StorageFor<T, Dyn>,
+    {
         Self {
-            data,
+            data: D::from_vec(data),
             _phantom: PhantomData,
         }
     }
 
     /// Creates an empty vector with dynamic length and with specified capacity.
-    pub fn with_capacity(capacity: usize) -> Self {
-        Self {
-            data: Vec::with_capacity(capacity),
-            _phantom: PhantomData,
-        }
+    #[cfg(feature = "alloc")]
+    pub fn with_capacity(capacity: usize) -> Self
+    where
+        D: StorageFor<T, Dyn>,
+    {
+        Self::from_vec(Vec::with_capacity(capacity))
     }
 
     /// Gets the number of elements.
@@ -69,41 +121,265 @@ impl<T> Vect<T, Dyn> {
         self.data.len()
     }
 
-    /// Converts a vector with static length.
+    /// Returns true if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Creates a vector with `n` clones of `elem`.
+    pub fn repeat(elem: T, n: usize) -> Self
+    where
+        T: Clone,
+        D: StorageFor<T, Dyn>,
+    {
+        Self {
+            data: D::from_exact_iter(iter::repeat_n(elem, n)),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Converts a vector with static length in the default storage.
     ///
     /// The vector size must be equal to the specified static size.
-    /// Otherwise it returns `None`.
-    pub fn into_static<S>(self) -> Option<Vect<T, S>>
+    /// Otherwise it returns a [LengthError] that gives the vector back.
+    pub fn into_static<S>(self) -> Result<Vect<T, S>, LengthError<Self>>
     where
         S: Unsigned + Size,
     {
-        let data = self.data;
+        if self.data.len() == S::USIZE {
+            Ok(Vect {
+                data: Storage::from_exact_iter(Storage::into_iter(self.data)),
+                _phantom: PhantomData,
+            })
+        } else {
+            Err(LengthError::new(S::USIZE, self.data.len(), self))
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Vect<T, Dyn> {
+    /// Removes an element from the end of the vector in place.
+    ///
+    /// It keeps the allocation, so that the vector can be reused.
+    pub fn pop_mut(&mut self) -> Option<T> {
+        Vec::pop(&mut self.data)
+    }
+
+    /// Calls the visitor with a vector of static length if the length is at most `Max`.
+    ///
+    /// The runtime length is matched against every static size from `U0` to `Max`.
+    /// On a match, the vector is converted by [into_static](Vect::into_static) and
+    /// passed to [visit](impls::StaticVisitor::visit). Otherwise, the vector is passed
+    /// to [visit_dyn](impls::StaticVisitor::visit_dyn).
+    pub fn dispatch<Max, V>(self, visitor: V) -> V::Output
+    where
+        Max: Unsigned + Size,
+        V: impls::StaticVisitor<T>,
+        (): impls::DispatchImpl<T, Max, V>,
+    {
+        if self.len() > Max::USIZE {
+            visitor.visit_dyn(self)
+        } else {
+            <() as impls::DispatchImpl<T, Max, V>>::impl_dispatch(self, visitor)
+        }
+    }
+}
+
+impl<T, D> Default for Vect<T, Dyn, D>
+where
+    D: StorageFor<T, Dyn>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, S, D> Vect<T, S, D>
+where
+    S: Unsigned + Size,
+    D: StorageFor<T, S>,
+{
+    /// Creates a vector with static length from [Vec].
+    ///
+    /// The vector size must be equal to the static size.
+    /// Otherwise it returns a [LengthError] that gives the vector back.
+    /// The unchecked [from_vec](Vect::from_vec) is only available for dynamic lengths.
+    ///
+    /// ```compile_fail,E0599
+    /// use type_vec::Vect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = Vect::<u8, U8>::from_vec(vec![]);
+    /// ```
+    pub fn try_from_vec(data: Vec<T>) -> Result<Self, DynLengthError<T>> {
         if data.len() == S::USIZE {
-            Some(Vect {
-                data,
+            Ok(Self {
+                data: D::from_vec(data),
                 _phantom: PhantomData,
             })
         } else {
-            None
+            Err(LengthError::new(S::USIZE, data.len(), Vect::from_vec(data)))
+        }
+    }
+}
+
+impl<T, S, D> Vect<T, S, D>
+where
+    S: Unsigned + Size,
+    D: Storage<T>,
+{
+    /// Creates a vector with static length whose elements are `f(0)`, `f(1)` and so on.
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+        D: StorageFor<T, S>,
+    {
+        Self {
+            data: D::from_exact_iter((0..S::USIZE).map(f)),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Creates a vector with static length filled with clones of `elem`.
+    pub fn from_elem(elem: T) -> Self
+    where
+        T: Clone,
+        D: StorageFor<T, S>,
+    {
+        Self {
+            data: D::from_exact_iter(iter::repeat_n(elem, S::USIZE)),
+            _phantom: PhantomData,
         }
     }
+
+    /// Creates a vector with static length from an iterator of known length.
+    ///
+    /// The iterator must yield exactly as many items as the static size. Otherwise it
+    /// returns a [LengthError] that gives the items back. The reported length of the
+    /// iterator is not trusted, so at most one item past the size is consumed.
+    pub fn from_iter_exact<I>(
+        iter: I,
+    ) -> Result<Self, LengthError<ExactIterItems<T, D, I::IntoIter>>>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+        D: StorageFor<T, S>,
+    {
+        let mut iter = iter.into_iter().peekable();
+        if iter.len() != S::USIZE {
+            let actual = iter.len();
+            return Err(LengthError::new(
+                S::USIZE,
+                actual,
+                None.into_iter().flatten().chain(iter),
+            ));
+        }
+
+        let data = D::from_exact_iter(iter.by_ref().take(S::USIZE));
+        let actual = match iter.peek() {
+            Some(_) => S::USIZE + 1,
+            None => data.len(),
+        };
+        if actual == S::USIZE {
+            return Ok(Self {
+                data,
+                _phantom: PhantomData,
+            });
+        }
+        let taken = IntoIter {
+            iter: Storage::into_iter(data),
+        };
+        Err(LengthError::new(
+            S::USIZE,
+            actual,
+            Some(taken).into_iter().flatten().chain(iter),
+        ))
+    }
+
+    /// Splits the vector into the first `I` elements and the rest.
+    ///
+    /// The index is static. It does not compile if the index exceeds the length.
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::Vect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = Vect::<usize, U3>::from([1, 2, 3]);
+    /// let (left, right) = vec.split_at(U4::new());
+    /// ```
+    pub fn split_at<I>(self, _index: I) -> impls::SplitAtOp<T, S, D, I>
+    where
+        I: Unsigned + Size,
+        S: Sub<I>,
+        Diff<S, I>: Size,
+    {
+        let mut iter = Storage::into_iter(self.data);
+        let left = Vect {
+            data: Storage::from_exact_iter(iter.by_ref().take(I::USIZE)),
+            _phantom: PhantomData,
+        };
+        let right = Vect {
+            data: Storage::from_exact_iter(iter),
+            _phantom: PhantomData,
+        };
+        (left, right)
+    }
+}
+
+impl<T, U, B, D> Default for Vect<T, UInt<U, B>, D>
+where
+    T: Default,
+    U: Unsigned,
+    B: Bit,
+    UInt<U, B>: Size,
+    D: StorageFor<T, UInt<U, B>>,
+{
+    fn default() -> Self {
+        Self::from_fn(|_| T::default())
+    }
 }
 
-impl<T, U, B> Vect<T, UInt<U, B>>
+impl<T, U, B, D> Vect<T, UInt<U, B>, D>
 where
     U: Unsigned,
     B: Bit,
+    UInt<U, B>: Size,
+    D: Storage<T>,
 {
     /// Gets the number of elements.
     pub fn len(&self) -> usize {
         UInt::<U, B>::USIZE
     }
+
+    /// Returns true if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
 }
 
-impl<T, S> Vect<T, S>
+impl<T, S, D> Vect<T, S, D>
 where
     S: Size,
+    D: Storage<T>,
 {
+    /// Creates a vector from [Vec] without checking the length.
+    ///
+    /// # Safety
+    /// If `S` is a static size, the length of `data` must be equal to it.
+    /// Otherwise, element accesses by static indices cause undefined behavior.
+    #[cfg(feature = "alloc")]
+    pub unsafe fn from_vec_unchecked(data: Vec<T>) -> Self
+    where
+        D: StorageFor<T, S>,
+    {
+        Self {
+            data: D::from_vec(data),
+            _phantom: PhantomData,
+        }
+    }
+
     /// Appends an element to the end of the vector.
     pub fn push(self, elem: T) -> impls::PushImplOp<Self, T>
     where
@@ -113,6 +389,18 @@ where
     }
 
     /// Removes an element from the end of the vector.
+    ///
+    /// If the length is static, it returns the vector and the element. Popping an empty
+    /// vector does not compile. If the length is dynamic, it returns a `Result`, which gives
+    /// the empty vector back on failure.
+    ///
+    /// ```compile_fail,E0599
+    /// use type_vec::Vect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = Vect::<usize, U0>::new();
+    /// vec.pop();
+    /// ```
     pub fn pop(self) -> impls::PopImplOp<Self>
     where
         (): impls::PopImpl<Self>,
@@ -123,6 +411,16 @@ where
     /// Returns a reference to an element depending on the index.
     ///
     /// If both length and index have static sizes, it returns `&T`. Otherwise, it returns `Option<&T>`.
+    /// The static index is checked in compile time, so that the inline storage is read
+    /// without runtime check.
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::Vect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = Vect::<usize, U4>::from([3, 1, 4, 1]);
+    /// let elem: &usize = vec.get(U4::new());
+    /// ```
     pub fn get<'a, I>(&'a self, index: I) -> impls::GetImplOp<'a, Self, I::Output>
     where
         I: IntoSize,
@@ -135,6 +433,7 @@ where
     ///
     /// If both length and index have static sizes, it checks if the index is valid in compile time.
     /// Otherwise, it panics if the index is out of bound.
+    /// Use [try_insert](Vect::try_insert) to recover from an invalid index.
     pub fn insert<I>(self, index: I, elem: T) -> impls::InsertImplOp<Self, I::Output, T>
     where
         I: IntoSize,
@@ -147,6 +446,7 @@ where
     ///
     /// If both length and index have static sizes, it checks if the index is valid in compile time.
     /// Otherwise, it panics if the index is out of bound.
+    /// Use [try_remove](Vect::try_remove) to recover from an invalid index.
     pub fn remove<I>(self, index: I) -> impls::RemoveImplOp<Self, I::Output>
     where
         I: IntoSize,
@@ -155,16 +455,423 @@ where
         <() as impls::RemoveImpl<Self, I::Output>>::impl_remove(self, index.into_size())
     }
 
-    /// Converts to a vector with dynamic length type.
+    /// Returns a reference to an element depending on the index without panic.
+    ///
+    /// If both length and index have static sizes, it returns `&T`. Otherwise, it returns
+    /// `Result<&T, IndexError>`.
+    ///
+    /// The static index past the length does not compile.
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::Vect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = Vect::<usize, U2>::from([1, 2]);
+    /// vec.try_get(U2::new());
+    /// ```
+    pub fn try_get<'a, I>(&'a self, index: I) -> impls::TryGetImplOp<'a, Self, I::Output>
+    where
+        I: IntoSize,
+        (): impls::TryGetImpl<'a, Self, I::Output>,
+    {
+        <() as impls::TryGetImpl<'a, Self, I::Output>>::impl_try_get(self, index.into_size())
+    }
+
+    /// Inserts an element at specified index without panic.
+    ///
+    /// If both length and index have static sizes, it checks if the index is valid in compile time
+    /// and returns the vector directly. Otherwise, it returns a `Result`, which gives the vector
+    /// and the element back in [IndexError](crate::IndexError) if the index is out of bound.
+    ///
+    /// The static index past the length does not compile.
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::Vect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = Vect::<usize, U2>::from([1, 2]);
+    /// vec.try_insert(U3::new(), 0);
+    /// ```
+    pub fn try_insert<I>(self, index: I, elem: T) -> impls::TryInsertImplOp<Self, I::Output, T>
+    where
+        I: IntoSize,
+        (): impls::TryInsertImpl<Self, I::Output, T>,
+    {
+        <() as impls::TryInsertImpl<Self, I::Output, T>>::impl_try_insert(
+            self,
+            index.into_size(),
+            elem,
+        )
+    }
+
+    /// Removes an element at specified index without panic.
+    ///
+    /// If both length and index have static sizes, it checks if the index is valid in compile time
+    /// and returns the vector and the element directly. Otherwise, it returns a `Result`, which
+    /// gives the vector back in [IndexError](crate::IndexError) if the index is out of bound.
+    ///
+    /// The static index past the length does not compile.
+    ///
+    /// ```compile_fail,E0277
+    /// use type_vec::Vect;
+    /// use typenum::consts::*;
+    ///
+    /// let vec = Vect::<usize, U2>::from([1, 2]);
+    /// vec.try_remove(U2::new());
+    /// ```
+    pub fn try_remove<I>(self, index: I) -> impls::TryRemoveImplOp<Self, I::Output>
+    where
+        I: IntoSize,
+        (): impls::TryRemoveImpl<Self, I::Output>,
+    {
+        <() as impls::TryRemoveImpl<Self, I::Output>>::impl_try_remove(self, index.into_size())
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Converts to a vector with dynamic length type in the default storage.
+    #[cfg(feature = "alloc")]
     pub fn into_dyn(self) -> Vect<T, Dyn> {
+        Vect::from_vec(self.data.into_vec())
+    }
+
+    /// Moves the elements to another storage while keeping the length.
+    pub fn into_storage<D2>(self) -> Vect<T, S, D2>
+    where
+        D2: StorageFor<T, S>,
+    {
         Vect {
-            data: self.data,
+            data: D2::from_exact_iter(Storage::into_iter(self.data)),
             _phantom: PhantomData,
         }
     }
 
-    /// Converts to [Vec](Vec).
+    /// Gets a reference to the storage.
+    pub fn storage(&self) -> &D {
+        &self.data
+    }
+
+    /// Converts to [Vec].
+    #[cfg(feature = "alloc")]
     pub fn into_vec(self) -> Vec<T> {
-        self.data
+        self.data.into_vec()
+    }
+}
+
+// standard traits
+
+impl<T, S, D> Debug for Vect<T, S, D>
+where
+    T: Debug,
+    S: Size,
+    D: Storage<T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+impl<T, S, D> Clone for Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T> + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, S, D> Copy for Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T> + Copy,
+{
+}
+
+/// Compares vectors of any sizes and storages. Vectors with different lengths are not equal.
+impl<A, B, S1, S2, D1, D2> PartialEq<Vect<B, S2, D2>> for Vect<A, S1, D1>
+where
+    A: PartialEq<B>,
+    S1: Size,
+    S2: Size,
+    D1: Storage<A>,
+    D2: Storage<B>,
+{
+    fn eq(&self, other: &Vect<B, S2, D2>) -> bool {
+        self.data.len() == other.data.len()
+            && self.data.iter().zip(other.data.iter()).all(|(l, r)| l == r)
+    }
+}
+
+impl<T, S, D> Eq for Vect<T, S, D>
+where
+    T: Eq,
+    S: Size,
+    D: Storage<T>,
+{
+}
+
+/// Compares vectors of any sizes and storages in lexicographic order.
+impl<T, S1, S2, D1, D2> PartialOrd<Vect<T, S2, D2>> for Vect<T, S1, D1>
+where
+    T: PartialOrd,
+    S1: Size,
+    S2: Size,
+    D1: Storage<T>,
+    D2: Storage<T>,
+{
+    fn partial_cmp(&self, other: &Vect<T, S2, D2>) -> Option<Ordering> {
+        self.data.iter().partial_cmp(other.data.iter())
+    }
+}
+
+impl<T, S, D> Ord for Vect<T, S, D>
+where
+    T: Ord,
+    S: Size,
+    D: Storage<T>,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.data.iter().cmp(other.data.iter())
+    }
+}
+
+impl<T, S, D> Hash for Vect<T, S, D>
+where
+    T: Hash,
+    S: Size,
+    D: Storage<T>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.data.hash_elements(state)
+    }
+}
+
+// comparison with standard types
+
+macro_rules! impl_partial_eq {
+    ([$($generics:tt)*] Vect, $rhs:ty) => {
+        impl<$($generics)* A, B, S, D> PartialEq<$rhs> for Vect<A, S, D>
+        where
+            A: PartialEq<B>,
+            S: Size,
+            D: Storage<A>,
+        {
+            fn eq(&self, other: &$rhs) -> bool {
+                let rhs: &[B] = other.as_ref();
+                self.data.len() == rhs.len() && self.data.iter().zip(rhs).all(|(l, r)| l == r)
+            }
+        }
+    };
+    ([$($generics:tt)*] $lhs:ty, Vect) => {
+        impl<$($generics)* A, B, S, D> PartialEq<Vect<B, S, D>> for $lhs
+        where
+            A: PartialEq<B>,
+            S: Size,
+            D: Storage<B>,
+        {
+            fn eq(&self, other: &Vect<B, S, D>) -> bool {
+                let lhs: &[A] = self.as_ref();
+                lhs.len() == other.data.len() && lhs.iter().zip(other.data.iter()).all(|(l, r)| l == r)
+            }
+        }
+    };
+}
+
+impl_partial_eq!([] Vect, [B]);
+impl_partial_eq!(['a,] Vect, &'a [B]);
+impl_partial_eq!(['a,] Vect, &'a mut [B]);
+#[cfg(feature = "alloc")]
+impl_partial_eq!([] Vect, Vec<B>);
+impl_partial_eq!([const N: usize,] Vect, [B; N]);
+impl_partial_eq!([][A], Vect);
+impl_partial_eq!(['a,] &'a [A], Vect);
+impl_partial_eq!(['a,] &'a mut [A], Vect);
+#[cfg(feature = "alloc")]
+impl_partial_eq!([] Vec<A>, Vect);
+impl_partial_eq!([const N: usize,] [A; N], Vect);
+
+// slice access
+
+impl<T, S, D> Deref for Vect<T, S, D>
+where
+    S: Size,
+    D: ContiguousStorage<T>,
+{
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.data.as_slice()
+    }
+}
+
+/// The length cannot be changed through a slice, so mutable access keeps `S` valid.
+impl<T, S, D> DerefMut for Vect<T, S, D>
+where
+    S: Size,
+    D: ContiguousStorage<T>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.data.as_mut_slice()
+    }
+}
+
+impl<T, S, D> AsRef<[T]> for Vect<T, S, D>
+where
+    S: Size,
+    D: ContiguousStorage<T>,
+{
+    fn as_ref(&self) -> &[T] {
+        self.data.as_slice()
+    }
+}
+
+impl<T, S, D> AsMut<[T]> for Vect<T, S, D>
+where
+    S: Size,
+    D: ContiguousStorage<T>,
+{
+    fn as_mut(&mut self) -> &mut [T] {
+        self.data.as_mut_slice()
+    }
+}
+
+impl<T, S, D> Borrow<[T]> for Vect<T, S, D>
+where
+    S: Size,
+    D: ContiguousStorage<T>,
+{
+    fn borrow(&self) -> &[T] {
+        self.data.as_slice()
+    }
+}
+
+impl<T, S, D> BorrowMut<[T]> for Vect<T, S, D>
+where
+    S: Size,
+    D: ContiguousStorage<T>,
+{
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.data.as_mut_slice()
+    }
+}
+
+// indexing
+
+impl<T, S, D> Index<usize> for Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        let len = self.data.len();
+        match self.data.get(index) {
+            Some(elem) => elem,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                len, index
+            ),
+        }
+    }
+}
+
+impl<T, S, D> IndexMut<usize> for Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+{
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let len = self.data.len();
+        match self.data.get_mut(index) {
+            Some(elem) => elem,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                len, index
+            ),
+        }
+    }
+}
+
+/// Indexes by a static index. It is checked against a static length in compile time,
+/// and against a dynamic length in runtime.
+impl<T, S, D> Index<UTerm> for Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+    (): size::CheckIndex<S, UTerm>,
+{
+    type Output = T;
+
+    fn index(&self, _index: UTerm) -> &Self::Output {
+        &self[UTerm::USIZE]
+    }
+}
+
+impl<T, S, D> IndexMut<UTerm> for Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+    (): size::CheckIndex<S, UTerm>,
+{
+    fn index_mut(&mut self, _index: UTerm) -> &mut Self::Output {
+        &mut self[UTerm::USIZE]
+    }
+}
+
+/// Indexes by a static index. It is checked against a static length in compile time,
+/// and against a dynamic length in runtime.
+///
+/// ```rust
+/// use type_vec::Vect;
+/// use typenum::consts::*;
+///
+/// let vec = Vect::<usize, U3>::from([3, 1, 4]);
+/// assert_eq!(vec[U2::new()], 4);
+/// ```
+///
+/// The index past a static length does not compile.
+///
+/// ```compile_fail,E0277
+/// use type_vec::Vect;
+/// use typenum::consts::*;
+///
+/// let vec = Vect::<usize, U3>::from([3, 1, 4]);
+/// let _ = vec[U3::new()];
+/// ```
+impl<T, S, D, U, B> Index<UInt<U, B>> for Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+    U: Unsigned,
+    B: Bit,
+    UInt<U, B>: Size,
+    (): size::CheckIndex<S, UInt<U, B>>,
+{
+    type Output = T;
+
+    fn index(&self, _index: UInt<U, B>) -> &Self::Output {
+        &self[UInt::<U, B>::USIZE]
+    }
+}
+
+impl<T, S, D, U, B> IndexMut<UInt<U, B>> for Vect<T, S, D>
+where
+    S: Size,
+    D: Storage<T>,
+    U: Unsigned,
+    B: Bit,
+    UInt<U, B>: Size,
+    (): size::CheckIndex<S, UInt<U, B>>,
+{
+    fn index_mut(&mut self, _index: UInt<U, B>) -> &mut Self::Output {
+        &mut self[UInt::<U, B>::USIZE]
     }
 }
@@ -106,3 +106,1087 @@ fn static_vec_test() {
     // switch to static length
     let _vec: Vect<usize, U4> = vec.into_static().unwrap();
 }
+
+#[test]
+fn dispatch_test() {
+    use type_vec::{Size, StaticVisitor};
+    use typenum::Unsigned;
+
+    struct SumVisitor;
+
+    impl StaticVisitor<usize> for SumVisitor {
+        type Output = (Option<usize>, usize);
+
+        fn visit<S>(self, vec: Vect<usize, S>) -> Self::Output
+        where
+            S: Unsigned + Size,
+        {
+            (Some(S::USIZE), vec.into_vec().into_iter().sum())
+        }
+
+        fn visit_dyn(self, vec: Vect<usize, Dyn>) -> Self::Output {
+            (None, vec.into_vec().into_iter().sum())
+        }
+    }
+
+    let vec = Vect::<usize, Dyn>::from_vec(vec![]);
+    assert_eq!(vec.dispatch::<U4, _>(SumVisitor), (Some(0), 0));
+
+    let vec = Vect::<usize, Dyn>::from_vec(vec![1, 2, 3]);
+    assert_eq!(vec.dispatch::<U4, _>(SumVisitor), (Some(3), 6));
+
+    let vec = Vect::<usize, Dyn>::from_vec(vec![1, 2, 3, 4]);
+    assert_eq!(vec.dispatch::<U4, _>(SumVisitor), (Some(4), 10));
+
+    let vec = Vect::<usize, Dyn>::from_vec(vec![1, 2, 3, 4, 5]);
+    assert_eq!(vec.dispatch::<U4, _>(SumVisitor), (None, 15));
+
+    let vec = Vect::<usize, Dyn>::from_vec(vec![1]);
+    assert_eq!(vec.dispatch::<U0, _>(SumVisitor), (None, 1));
+}
+
+#[test]
+fn dyn_size_test() {
+    use std::collections::HashMap;
+    use type_vec::{size::SizeMulOp, Size};
+
+    let size = Dyn::new(3);
+    assert_eq!(size.get(), 3);
+    assert_eq!(size.to_usize(), 3);
+    assert_eq!(format!("{}", size), "3");
+    assert_eq!(format!("{:?}", size), "Dyn(3)");
+
+    // arithmetic follows the type-level rules
+    let sum: Dyn = size + U2::new();
+    assert_eq!(sum, Dyn::new(5));
+    let sum: Dyn = U2::new() + size;
+    assert_eq!(sum, Dyn::new(5));
+    let prod: Dyn = size * Dyn::new(4);
+    assert_eq!(prod, Dyn::new(12));
+    let _: SizeMulOp<U3, Dyn> = Dyn::new(0);
+    let _: SizeMulOp<U3, U2> = U6::new();
+    assert_eq!(size.checked_sub(U3::new()), Some(Dyn::new(0)));
+    assert_eq!(size.checked_sub(U4::new()), None);
+
+    // overflow panics instead of wrapping
+    let result = std::panic::catch_unwind(|| Dyn::new(usize::MAX) + U1::new());
+    assert!(result.is_err());
+    let result = std::panic::catch_unwind(|| U2::new() * Dyn::new(usize::MAX));
+    assert!(result.is_err());
+
+    // cross comparison with static sizes
+    assert!(size == U3::new());
+    assert!(U3::new() == size);
+    assert!(size < U4::new());
+    assert!(U0::new() < size);
+
+    // usable as hash map key
+    let mut map = HashMap::new();
+    map.insert(size, "three");
+    assert_eq!(map.get(&Dyn::new(3)), Some(&"three"));
+
+    // derived traits on Vect now apply to dynamic vectors
+    let vec = Vect::<usize, Dyn>::from_vec(vec![1, 2]);
+    let cloned = vec.clone();
+    assert_eq!(vec, cloned);
+    assert!(format!("{:?}", vec).contains("[1, 2]"));
+}
+
+#[test]
+fn predicate_test() {
+    use type_vec::{
+        predicate::{
+            AssertSizeLe, AssertSizeLt, AssertStatic, IsStaticOp, SizeEqOp, SizeLeOp, SizeLtOp,
+        },
+        Size,
+    };
+    use typenum::{Bit, B0, B1};
+
+    fn bit<B: Bit>() -> bool {
+        B::BOOL
+    }
+
+    fn static_len<S>(_vec: &Vect<usize, S>) -> usize
+    where
+        S: Size + typenum::Unsigned,
+        (): AssertStatic<S>,
+    {
+        S::USIZE
+    }
+
+    fn fits<S, Cap>(_vec: &Vect<usize, S>) -> bool
+    where
+        S: Size,
+        Cap: Size,
+        (): AssertSizeLe<S, Cap> + AssertSizeLt<U0, Cap>,
+    {
+        true
+    }
+
+    assert!(bit::<IsStaticOp<U3>>());
+    assert!(!bit::<IsStaticOp<Dyn>>());
+
+    assert!(bit::<SizeEqOp<U3, U3>>());
+    assert!(!bit::<SizeEqOp<U3, U4>>());
+    assert!(bit::<SizeEqOp<U3, Dyn>>());
+    assert!(bit::<SizeEqOp<Dyn, Dyn>>());
+
+    assert!(bit::<SizeLtOp<U3, U4>>());
+    assert!(!bit::<SizeLtOp<U4, U4>>());
+    assert!(bit::<SizeLtOp<Dyn, U0>>());
+
+    assert!(bit::<SizeLeOp<U4, U4>>());
+    assert!(!bit::<SizeLeOp<U5, U4>>());
+
+    let _: B1 = <SizeEqOp<U0, U0>>::new();
+    let _: B0 = <SizeLeOp<U1, U0>>::new();
+
+    let vec = Vect::<usize, U0>::new().push(1).push(2);
+    assert_eq!(static_len(&vec), 2);
+    assert!(fits::<_, U2>(&vec));
+    assert!(fits::<_, U2>(&vec.into_dyn()));
+}
+
+#[test]
+fn size_macro_test() {
+    use type_vec::{size, size_bounds, Size};
+    use typenum::Unsigned;
+
+    size_bounds! {
+        [(N + M) * 2]
+        fn concat_twice<N, M>(lhs: Vect<usize, N>, rhs: Vect<usize, M>) -> Vect<usize, size!((N + M) * 2)>
+        where
+            N: Size,
+            M: Size,
+            size!((N + M) * 2): Unsigned,
+        {
+            let lhs = lhs.into_vec();
+            let rhs = rhs.into_vec();
+            let mut data = lhs.clone();
+            data.extend(rhs.iter().cloned());
+            data.extend(lhs);
+            data.extend(rhs);
+            Vect::<usize, Dyn>::from_vec(data).into_static().unwrap()
+        }
+    }
+
+    let lhs = Vect::<usize, U0>::new().push(1);
+    let rhs = Vect::<usize, U0>::new().push(2).push(3);
+    let vec: Vect<usize, U6> = concat_twice(lhs, rhs);
+    assert_eq!(vec.into_vec(), vec![1, 2, 3, 1, 2, 3]);
+
+    let _: size!(1) = U1::new();
+    let _: size!(U3 * 2 + 1) = U7::new();
+    let _: size!(2 * U3 + 1) = U7::new();
+    let _: size!((U3 + 1) * (1 + 1)) = U8::new();
+    let _: size!(U3 + Dyn) = Dyn::new(0);
+    let _: size!(Dyn * 0 + 1) = Dyn::new(0);
+}
+
+#[test]
+fn std_traits_test() {
+    use std::{
+        borrow::Borrow,
+        collections::{hash_map::DefaultHasher, HashSet},
+        hash::{Hash, Hasher},
+    };
+
+    struct NotClone;
+
+    // traits only bound the element type
+    let vec = Vect::<usize, Dyn>::from_vec(vec![3, 1, 4]);
+    let cloned = vec.clone();
+    assert_eq!(vec, cloned);
+    assert_eq!(format!("{:?}", vec), "[3, 1, 4]");
+    assert!(vec < Vect::<usize, Dyn>::from_vec(vec![3, 2]));
+
+    let mut set = HashSet::new();
+    set.insert(vec.clone());
+    assert!(set.contains(&cloned));
+    let _ = Vect::<NotClone, Dyn>::new();
+
+    // hashes the same as the slice for Borrow
+    let mut lhs = DefaultHasher::new();
+    let mut rhs = DefaultHasher::new();
+    vec.hash(&mut lhs);
+    <Vect<usize, Dyn> as Borrow<[usize]>>::borrow(&vec).hash(&mut rhs);
+    assert_eq!(lhs.finish(), rhs.finish());
+
+    // slice access
+    let mut vec: Vect<usize, U3> = vec.into_static().unwrap();
+    assert_eq!(vec.iter().sum::<usize>(), 8);
+    assert_eq!(vec.as_ref(), &[3, 1, 4]);
+    vec.sort();
+    assert_eq!(&*vec, &[1, 3, 4]);
+
+    // indexing
+    assert_eq!(vec[0], 1);
+    assert_eq!(vec[U2::new()], 4);
+    vec[U0::new()] = 7;
+    vec[1] = 8;
+    assert_eq!(&*vec, &[7, 8, 4]);
+    let vec = vec.into_dyn();
+    assert_eq!(vec[U1::new()], 8);
+    let result = std::panic::catch_unwind(|| vec[U5::new()]);
+    assert!(result.is_err());
+
+    // default
+    let vec: Vect<usize, U0> = Default::default();
+    assert!(vec.is_empty());
+    let vec: Vect<usize, Dyn> = Default::default();
+    assert!(vec.is_empty());
+}
+
+#[test]
+fn iter_test() {
+    use std::iter::FusedIterator;
+
+    fn assert_iter<I>(_: &I)
+    where
+        I: ExactSizeIterator + DoubleEndedIterator + FusedIterator,
+    {
+    }
+
+    let mut vec = Vect::<usize, U0>::new().push(1).push(2).push(3);
+
+    for elem in &mut vec {
+        *elem *= 10;
+    }
+    let iter = vec.iter();
+    assert_iter(&iter);
+    assert_eq!(iter.len(), 3);
+    assert_eq!(
+        (&vec).into_iter().rev().copied().collect::<Vec<_>>(),
+        vec![30, 20, 10]
+    );
+
+    let mut iter = vec.into_iter();
+    assert_iter(&iter);
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some(30));
+    assert_eq!(iter.as_slice(), &[10, 20]);
+    assert_eq!(iter.collect::<Vec<_>>(), vec![10, 20]);
+
+    let mut vec: Vect<usize, Dyn> = (0..3).collect();
+    vec.extend(3..5);
+    vec.extend(&[5, 6]);
+    assert_eq!(&*vec, &[0, 1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn collect_exact_test() {
+    use type_vec::IteratorExt;
+
+    let vec: Vect<usize, U3> = (0..3).collect_exact().unwrap();
+    assert_eq!(&*vec, &[0, 1, 2]);
+
+    // too short
+    let err = (0..2).collect_exact::<U3>().unwrap_err();
+    assert_eq!((err.expected, err.actual), (3, 2));
+    assert_eq!(err.to_string(), "expected length 3, but found 2");
+    assert_eq!(&*err.into_data(), &[0, 1]);
+
+    // too long, consumes only one surplus item
+    let mut iter = 0..10;
+    let err = iter.by_ref().collect_exact::<U3>().unwrap_err();
+    assert_eq!((err.expected, err.actual), (3, 4));
+    assert_eq!(&*err.data, &[0, 1, 2, 3]);
+    assert_eq!(iter.next(), Some(4));
+
+    // at most
+    let vec = (0..2).collect_at_most::<U3>().unwrap();
+    assert_eq!(&*vec, &[0, 1]);
+    let mut iter = 0..10;
+    let err = iter.by_ref().collect_at_most::<U3>().unwrap_err();
+    assert_eq!((err.expected, err.actual), (3, 4));
+    assert_eq!(iter.next(), Some(4));
+}
+
+#[test]
+fn typed_iter_test() {
+    let lhs = Vect::<usize, U0>::new().push(1).push(2).push(3);
+    let rhs = Vect::<usize, U0>::new().push(4).push(5);
+
+    let sums: Vect<usize, U3> = lhs
+        .typed_iter()
+        .zip(lhs.typed_iter().rev())
+        .map(|(l, r)| l + r)
+        .collect_vect();
+    assert_eq!(&*sums, &[4, 4, 4]);
+
+    let chained: Vect<usize, U5> = lhs
+        .typed_iter()
+        .chain(rhs.typed_iter())
+        .cloned()
+        .collect_vect();
+    assert_eq!(&*chained, &[1, 2, 3, 4, 5]);
+
+    let indexed: Vect<(usize, usize), U2> = rhs.into_typed_iter().enumerate().collect_vect();
+    assert_eq!(&*indexed, &[(0, 4), (1, 5)]);
+
+    let mut vec = lhs.into_dyn();
+    vec.typed_iter_mut()
+        .map(|elem| *elem += 1)
+        .into_iter()
+        .for_each(drop);
+    let doubled: Vect<usize, Dyn> = vec.typed_iter().copied().map(|x| x * 2).collect_vect();
+    assert_eq!(&*doubled, &[4, 6, 8]);
+}
+
+#[test]
+fn convert_test() {
+    use std::convert::{TryFrom, TryInto};
+
+    // arrays
+    let vec: Vect<usize, U3> = [1, 2, 3].into();
+    let array: [usize; 3] = vec.into();
+    assert_eq!(array, [1, 2, 3]);
+    let vec: Vect<usize, U0> = Vect::from([0usize; 0]);
+    assert!(vec.is_empty());
+
+    // vectors
+    let vec: Vect<usize, U3> = vec![1, 2, 3].try_into().unwrap();
+    let data: Vec<usize> = vec.into();
+    let err = Vect::<usize, U2>::try_from(data).unwrap_err();
+    assert_eq!((err.expected, err.actual), (2, 3));
+    assert_eq!(err.into_data().into_vec(), vec![1, 2, 3]);
+    let vec: Vect<usize, Dyn> = vec![1, 2].into();
+    assert_eq!(vec.len(), 2);
+
+    // boxed slices
+    let boxed: Box<[usize]> = vec![4, 5].into_boxed_slice();
+    let vec = Vect::<usize, U2>::try_from(boxed).unwrap();
+    let boxed: Box<[usize]> = vec.into();
+    let err = Vect::<usize, U3>::try_from(boxed).unwrap_err();
+    assert_eq!(&*err.data, &[4, 5]);
+    let vec: Vect<usize, Dyn> = err.into_data().into();
+    assert_eq!(vec.len(), 2);
+
+    // slices
+    let slice: &[usize] = &[7, 8, 9];
+    let vec = Vect::<usize, U3>::try_from(slice).unwrap();
+    assert_eq!(&*vec, slice);
+    let err = Vect::<usize, U4>::try_from(slice).unwrap_err();
+    assert_eq!(err.to_string(), "expected length 4, but found 3");
+    assert_eq!(err.data, slice);
+
+    // copy-on-write slices
+    let cow: std::borrow::Cow<[usize]> = vec.into();
+    let vec: Vect<usize, Dyn> = cow.into();
+    assert_eq!(&*vec, slice);
+}
+
+#[test]
+fn cross_size_cmp_test() {
+    let expected = Vect::<u8, U0>::new().push(1).push(2).push(3).push(4);
+    let parsed = Vect::<u8, Dyn>::from_vec(vec![1, 2, 3, 4]);
+
+    assert_eq!(parsed, expected);
+    assert_eq!(expected, parsed);
+    assert_ne!(Vect::<u8, Dyn>::from_vec(vec![1, 2]), expected);
+    assert_ne!(expected, Vect::<u8, U0>::new().push(1));
+    assert!(Vect::<u8, Dyn>::from_vec(vec![1, 2, 4]) > expected);
+    assert!(expected < Vect::<u8, U0>::new().push(2));
+
+    // standard types
+    assert_eq!(expected, [1, 2, 3, 4]);
+    assert_eq!([1, 2, 3, 4], expected);
+    assert_eq!(expected, vec![1, 2, 3, 4]);
+    assert_eq!(vec![1, 2, 3, 4], parsed);
+    assert_eq!(parsed, &[1u8, 2, 3, 4][..]);
+    assert_eq!(&[1u8, 2, 3, 4][..], parsed);
+    assert_ne!(expected, [1, 2, 3]);
+}
+
+#[test]
+fn error_test() {
+    use std::collections::VecDeque;
+    use type_vec::{Error, LengthError};
+
+    let vec = Vect::<usize, Dyn>::from_vec(vec![1, 2, 3]);
+    let err: LengthError<Vect<usize, Dyn>> = vec.into_static::<U2>().unwrap_err();
+    assert_eq!((err.expected, err.actual), (2, 3));
+
+    let err: Error<usize> = err.into();
+    assert_eq!(err.to_string(), "expected length 2, but found 3");
+    #[cfg(feature = "std")]
+    {
+        use std::error::Error as _;
+
+        assert!(err.source().is_none());
+        let boxed: Box<dyn std::error::Error> = Box::new(err.clone());
+        assert_eq!(boxed.to_string(), err.to_string());
+    }
+
+    // the buffer is recovered
+    let vec = err.into_data();
+    assert_eq!(vec, [1, 2, 3]);
+    let vec: Vect<usize, U3> = vec.into_static().unwrap();
+    assert_eq!(vec, [1, 2, 3]);
+
+    // errors on vectors in other storages convert as well
+    let vec = Vect::<usize, Dyn, VecDeque<usize>>::from_vec(vec![1, 2]);
+    let err: Error<usize> = vec.into_static::<U3>().unwrap_err().into();
+    assert_eq!(err.into_data(), [1, 2]);
+
+    // fallible operations convert into the crate error
+    fn edit(vec: Vect<usize, Dyn, VecDeque<usize>>, index: usize) -> Result<usize, Error<usize>> {
+        let vec = vec.try_insert(index, 9)?;
+        let (vec, elem) = vec.try_remove(index)?;
+        let _: Vect<usize, U4, _> = vec.into_static()?;
+        Ok(elem)
+    }
+
+    let vec = Vect::<usize, Dyn, VecDeque<usize>>::from_vec(vec![1, 2, 3, 4]);
+    assert_eq!(edit(vec.clone(), 2), Ok(9));
+    match edit(vec, 5) {
+        Err(Error::Insert(err)) => {
+            assert_eq!((err.index, err.len), (5, 4));
+            assert_eq!(err.data, (Vect::from_vec(vec![1, 2, 3, 4]), 9));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    let vec = Vect::<usize, Dyn, VecDeque<usize>>::from_vec(vec![2, 3, 4]);
+    let err = edit(vec, 0).unwrap_err();
+    assert!(matches!(err, Error::Length(_)));
+    assert_eq!(err.into_data(), [2, 3, 4]);
+}
+
+#[test]
+fn try_index_test() {
+    use type_vec::IndexError;
+
+    let vec = Vect::<usize, U0>::new().push(1).push(2);
+
+    // static index returns plain values
+    let elem: &usize = vec.try_get(U1::new());
+    assert_eq!(elem, &2);
+    let vec: Vect<usize, U3> = vec.try_insert(U2::new(), 3);
+    let (vec, elem): (Vect<usize, U2>, usize) = vec.try_remove(U0::new());
+    assert_eq!(elem, 1);
+
+    // dynamic index returns results
+    assert_eq!(vec.try_get(1), Ok(&3));
+    let err: IndexError = vec.try_get(2).unwrap_err();
+    assert_eq!((err.index, err.len), (2, 2));
+    assert_eq!(err.to_string(), "index 2 is out of bound for length 2");
+
+    let err = vec.try_insert(3, 9).unwrap_err();
+    let (vec, elem): (Vect<usize, U2>, usize) = err.into_data();
+    assert_eq!(elem, 9);
+    let vec: Vect<usize, U3> = vec.try_insert(2, 4).unwrap();
+
+    let err = vec.try_remove(3).unwrap_err();
+    let vec: Vect<usize, U3> = err.into_data();
+    let (vec, elem): (Vect<usize, U2>, usize) = vec.try_remove(1).unwrap();
+    assert_eq!(elem, 3);
+
+    // dynamic length
+    let vec = vec.into_dyn();
+    let vec: Vect<usize, Dyn> = vec.try_insert(U2::new(), 5).unwrap();
+    let (vec, elem): (Vect<usize, Dyn>, usize) = vec.try_remove(U0::new()).unwrap();
+    assert_eq!(elem, 2);
+    let err: type_vec::Error<usize> = vec.try_remove(5).unwrap_err().into();
+    assert_eq!(err.into_data(), [4, 5]);
+}
+
+#[test]
+fn dyn_pop_test() {
+    let mut vec = Vect::<usize, Dyn>::with_capacity(64);
+    vec.extend(0..2);
+
+    let (vec, elem) = vec.pop().unwrap();
+    assert_eq!(elem, 1);
+    let (vec, elem) = vec.pop().unwrap();
+    assert_eq!(elem, 0);
+
+    // the empty vector is given back with its allocation
+    let mut vec: Vect<usize, Dyn> = vec.pop().unwrap_err();
+    assert!(vec.is_empty());
+    assert!(vec.capacity() >= 64);
+
+    vec.extend(0..3);
+    assert_eq!(vec.pop_mut(), Some(2));
+    assert_eq!(vec.pop_mut(), Some(1));
+    assert_eq!(vec.pop_mut(), Some(0));
+    assert_eq!(vec.pop_mut(), None);
+    assert!(vec.capacity() >= 64);
+}
+
+#[test]
+fn checked_from_vec_test() {
+    let vec = Vect::<u8, U3>::try_from_vec(vec![1, 2, 3]).unwrap();
+    assert_eq!(vec.get(U2::new()), &3);
+
+    let err = Vect::<u8, U8>::try_from_vec(vec![]).unwrap_err();
+    assert_eq!((err.expected, err.actual), (8, 0));
+
+    let vec = unsafe { Vect::<u8, U2>::from_vec_unchecked(vec![4, 5]) };
+    assert_eq!(vec.get(U1::new()), &5);
+}
+
+#[test]
+fn inline_storage_test() {
+    use std::{mem, rc::Rc};
+    use type_vec::{Array, Storage};
+
+    // static vectors are stored inline and copied like arrays
+    assert_eq!(mem::size_of::<Vect<u32, U4>>(), mem::size_of::<[u32; 4]>());
+    assert_eq!(mem::size_of::<Vect<u8, U0>>(), 0);
+
+    let vec: Vect<u32, U4> = [1, 2, 3, 4].into();
+    let copy = vec;
+    assert_eq!(vec, copy);
+    assert_eq!(vec.capacity(), 4);
+
+    let vec = vec.push(5).insert(U0::new(), 0);
+    let (vec, elem) = vec.remove(U2::new());
+    assert_eq!(elem, 2);
+    let (vec, elem) = vec.pop();
+    assert_eq!(elem, 5);
+    assert_eq!(vec, [0, 1, 3, 4]);
+
+    // the data moves to the heap on conversion to dynamic length
+    let vec = vec.into_dyn().push(6);
+    assert_eq!(vec, [0, 1, 3, 4, 6]);
+
+    // every element is dropped exactly once
+    let elem = Rc::new(());
+    {
+        let vec = Vect::<Rc<()>, U0>::new()
+            .push(elem.clone())
+            .push(elem.clone())
+            .insert(1, elem.clone());
+        let (vec, _) = vec.remove(U0::new());
+        let (vec, _) = vec.pop();
+        assert_eq!(Rc::strong_count(&elem), 2);
+        let mut iter = vec.push(elem.clone()).into_iter();
+        iter.next();
+        assert_eq!(Rc::strong_count(&elem), 2);
+    }
+    assert_eq!(Rc::strong_count(&elem), 1);
+
+    // the array is built from exactly as many items as its length
+    let elem = Rc::new(());
+    let result = std::panic::catch_unwind(|| {
+        Array::<Rc<()>, U2>::from_exact_iter(vec![elem.clone(), elem.clone(), elem.clone()])
+    });
+    assert!(result.is_err());
+    assert_eq!(Rc::strong_count(&elem), 1);
+}
+
+#[test]
+fn storage_test() {
+    use std::{
+        collections::{hash_map::DefaultHasher, VecDeque},
+        hash::{Hash, Hasher},
+        rc::Rc,
+    };
+
+    fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // heap storage with static length
+    let vec = Vect::<usize, U0, Vec<usize>>::with_capacity(8)
+        .push(1)
+        .push(2);
+    assert!(vec.capacity() >= 8);
+    let vec: Vect<usize, U3, Vec<usize>> = vec.insert(U0::new(), 0);
+    assert_eq!(vec, [0, 1, 2]);
+
+    // boxed slice
+    let vec: Vect<usize, U3, Box<[usize]>> = vec.into_storage();
+    let (vec, elem) = vec.pop();
+    assert_eq!(elem, 2);
+    assert_eq!(&*vec, &[0, 1]);
+
+    // deque is not contiguous, but supports every operation
+    let vec: Vect<usize, U2, VecDeque<usize>> = vec.into_storage();
+    let vec = vec.insert(U0::new(), 5).push(6);
+    let (vec, elem) = vec.remove(U1::new());
+    assert_eq!(elem, 0);
+    assert_eq!(vec.get(U0::new()), &5);
+    assert_eq!(vec.get(3), None);
+    assert_eq!(vec[2], 6);
+    assert_eq!(vec.iter().copied().collect::<Vec<_>>(), [5, 1, 6]);
+    assert_eq!(format!("{:?}", vec), "[5, 1, 6]");
+
+    // vectors compare and hash alike across storages
+    let inline: Vect<usize, U3> = vec.clone().into_storage();
+    assert_eq!(vec, inline);
+    assert_eq!(hash_of(&vec), hash_of(&inline));
+    assert_eq!(hash_of(&inline), hash_of(&[5usize, 1, 6][..]));
+
+    // shared slice clones cheaply and copies on write
+    let shared: Vect<usize, U3, Rc<[usize]>> = inline.into_storage();
+    let mut copy = shared.clone();
+    assert!(Rc::ptr_eq(shared.storage(), copy.storage()));
+    copy[U0::new()] = 7;
+    assert!(!Rc::ptr_eq(shared.storage(), copy.storage()));
+    assert_eq!(shared, [5, 1, 6]);
+    assert_eq!(copy, [7, 1, 6]);
+
+    // dynamic length with another storage
+    let vec: Vect<usize, Dyn, VecDeque<usize>> = (0..3).collect();
+    let vec = vec.push(3);
+    assert_eq!(vec.len(), 4);
+    let vec: Vect<usize, U4> = vec.into_static().unwrap();
+    assert_eq!(vec, [0, 1, 2, 3]);
+}
+
+#[test]
+fn persistent_storage_test() {
+    use type_vec::storage::Persistent;
+
+    // old versions are kept while new versions are derived
+    let v0 = Vect::<usize, U0, Persistent<usize>>::new();
+    let v2 = v0.push(1).push(2);
+    let v3 = v2.clone().push(3);
+    let (v1, elem) = v2.clone().pop();
+    assert_eq!(elem, 2);
+    let v3b: Vect<usize, U3, Persistent<usize>> = v2.clone().insert(U1::new(), 5);
+    let (v1b, elem) = v3.clone().remove(U0::new());
+    assert_eq!(elem, 1);
+    assert_eq!(v1, [1]);
+    assert_eq!(v2, [1, 2]);
+    assert_eq!(v3, [1, 2, 3]);
+    assert_eq!(v3b, [1, 5, 2]);
+    assert_eq!(v1b, [2, 3]);
+
+    // clones share the root until modified
+    let mut copy = v3.clone();
+    assert!(copy.storage().ptr_eq(v3.storage()));
+    copy[U2::new()] = 7;
+    assert!(!copy.storage().ptr_eq(v3.storage()));
+    assert_eq!(copy, [1, 2, 7]);
+    assert_eq!(v3, [1, 2, 3]);
+    assert_eq!(format!("{:?}", copy), "[1, 2, 7]");
+
+    // deep tries across several levels
+    let len = 32 * 32 * 2 + 5;
+    let vec: Vect<usize, Dyn, Persistent<usize>> = (0..len).collect();
+    assert_eq!(vec.len(), len);
+    assert!(vec.iter().copied().eq(0..len));
+    assert!(vec.iter().rev().copied().eq((0..len).rev()));
+    assert_eq!(vec.iter().len(), len);
+    assert_eq!(vec.get(1500), Some(&1500));
+    assert_eq!(vec.get(len), None);
+
+    let mut iter = vec.iter();
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.next_back(), Some(&(len - 1)));
+    assert_eq!(iter.len(), len - 2);
+
+    let mut doubled = vec.clone();
+    doubled.iter_mut().for_each(|elem| *elem *= 2);
+    assert!(doubled.iter().copied().eq((0..len).map(|elem| elem * 2)));
+    assert!(vec.iter().copied().eq(0..len));
+
+    let (vec, elem) = vec.insert(100, 0).remove(len);
+    assert_eq!(elem, len - 1);
+    let expect: Vec<_> = (0..100).chain(Some(0)).chain(100..len - 1).collect();
+    assert_eq!(vec, expect);
+
+    let mut vec = vec;
+    let mut expect = expect;
+    while let Ok((rest, elem)) = vec.pop() {
+        assert_eq!(Some(elem), expect.pop());
+        vec = rest;
+    }
+    assert!(expect.is_empty());
+
+    // elements are moved out when not shared, and cloned otherwise
+    let vec: Vect<usize, Dyn, Persistent<usize>> = (0..100).collect();
+    let copy = vec.clone();
+    assert_eq!(vec.into_vec(), (0..100).collect::<Vec<_>>());
+    assert!(copy.into_iter().eq(0..100));
+
+    // insertion and removal anywhere keep the old versions intact
+    let mut vec: Vect<usize, Dyn, Persistent<usize>> = (0..len).collect();
+    let mut expect: Vec<_> = (0..len).collect();
+    let mut versions = vec![];
+    let mut seed = 1usize;
+    for step in 0..3000 {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let index = (seed >> 33) % (expect.len() + 1);
+        if step % 3 == 0 && index < expect.len() {
+            let (rest, elem) = vec.remove(index);
+            assert_eq!(elem, expect.remove(index));
+            vec = rest;
+        } else {
+            vec = vec.insert(index, step);
+            expect.insert(index, step);
+        }
+        if step % 500 == 0 {
+            versions.push((vec.clone(), expect.clone()));
+        }
+    }
+    assert_eq!(vec, expect);
+    assert!(vec.iter().rev().eq(expect.iter().rev()));
+    for (vec, expect) in versions {
+        assert_eq!(vec, expect);
+    }
+
+    // updates on a clone copy only the path to the index
+    #[derive(Debug, PartialEq)]
+    struct Counted(usize);
+
+    thread_local! {
+        static CLONES: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            CLONES.with(|clones| clones.set(clones.get() + 1));
+            Self(self.0)
+        }
+    }
+
+    let vec: Vect<Counted, Dyn, Persistent<Counted>> = (0..100_000).map(Counted).collect();
+    let clones = CLONES.with(|clones| clones.replace(0));
+    assert_eq!(clones, 0);
+    let inserted = vec.clone().insert(1, Counted(0));
+    let (removed, _) = vec.clone().remove(1);
+    assert!(CLONES.with(|clones| clones.get()) <= 4 * 32);
+    assert_eq!(inserted.get(2), Some(&Counted(1)));
+    assert_eq!(removed.get(1), Some(&Counted(2)));
+    assert_eq!(vec.len(), 100_000);
+    assert_eq!(vec.get(1), Some(&Counted(1)));
+}
+
+#[test]
+fn capacity_test() {
+    use type_vec::CapVect;
+
+    let vec = CapVect::<usize, U0, U4>::new();
+    assert_eq!(vec.capacity(), 4);
+    let ptr = vec.as_ptr();
+
+    // typed operations never reallocate
+    let vec = vec.push(1).push(2).push(3);
+    let vec: CapVect<usize, U4, U4> = vec.insert(U0::new(), 0);
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(*vec, [0, 1, 2, 3]);
+
+    let (vec, elem) = vec.remove(U1::new());
+    assert_eq!(elem, 1);
+    let (vec, elem) = vec.pop();
+    assert_eq!(elem, 3);
+    let mut vec = vec.push(5);
+    assert_eq!(vec.as_ptr(), ptr);
+
+    // element accesses go through the underlying vector
+    assert_eq!(vec.get(U2::new()), &5);
+    vec[U0::new()] = 4;
+    vec.as_mut_slice()[1] += 1;
+    vec[1] -= 1;
+    assert_eq!(format!("{:?}", vec), "[4, 2, 5]");
+    assert!(vec.clone().into_vect().capacity() >= 4);
+
+    // erase and restore the capacity
+    let vect: Vect<usize, U3, Vec<usize>> = vec.into_vect();
+    assert_eq!(vect, [4, 2, 5]);
+    let vec = CapVect::<usize, U3, U8>::from_vect(vect);
+    assert!(vec.as_vect().capacity() >= 8);
+    let vec = vec.push(6).push(7);
+    assert_eq!(*vec, [4, 2, 5, 6, 7]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn pool_test() {
+    use type_vec::VectPool;
+
+    let pool = VectPool::<f32>::new();
+    assert!(pool.is_empty());
+
+    // buffers are given back on drop
+    let vec = pool.with_capacity(16).push(1.0).push(2.0);
+    let vec: Vect<f32, U3, _> = vec.insert(U0::new(), 0.0);
+    assert_eq!(vec, [0.0, 1.0, 2.0]);
+    let ptr = vec.as_ptr();
+    let capacity = vec.capacity();
+    drop(vec);
+    assert_eq!(pool.len(), 1);
+
+    let vec = pool.empty_dyn();
+    assert!(pool.is_empty());
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), capacity);
+    assert!(vec.is_empty());
+
+    // clones take their buffers from the same pool
+    let vec = vec.push(3.0);
+    let copy = vec.clone();
+    assert_eq!(copy, [3.0]);
+    assert!(copy.storage().pool().is_some());
+    drop(copy);
+    drop(vec);
+    assert_eq!(pool.len(), 2);
+
+    // converting into a Vec detaches the buffer
+    let vec = pool.empty().push(4.0).into_vec();
+    assert_eq!(vec, [4.0]);
+    assert_eq!(pool.len(), 1);
+
+    // other vectors give their buffers with into_pool
+    Vect::<f32, Dyn>::from_vec(vec).into_pool(&pool);
+    Vect::<f32, U0, Vec<f32>>::with_capacity(8).into_pool(&pool);
+    Vect::<f32, Dyn>::new().into_pool(&pool);
+    assert_eq!(pool.len(), 3);
+
+    pool.clear();
+    assert!(pool.is_empty());
+
+    // inline vectors have no buffer to give
+    Vect::<f32, U2>::from([1.0, 2.0]).into_pool(&pool);
+    assert!(pool.is_empty());
+
+    // the pool keeps at most limit buffers
+    let pool = VectPool::<f32>::with_limit(2);
+    assert_eq!(pool.limit(), 2);
+    let vecs: Vec<_> = (0..4)
+        .map(|_| pool.with_capacity_dyn(8).push(0.0))
+        .collect();
+    drop(vecs);
+    assert_eq!(pool.len(), 2);
+    assert_eq!(
+        VectPool::<f32>::new().limit(),
+        VectPool::<f32>::DEFAULT_LIMIT
+    );
+
+    // vectors are sent to other threads and give their buffers back from there
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    let pool = VectPool::<f32>::new();
+    let vec = pool.with_capacity(8).push(1.0);
+    assert_send_sync(&pool);
+    assert_send_sync(&vec);
+    let sum = std::thread::spawn(move || vec.iter().sum::<f32>())
+        .join()
+        .unwrap();
+    assert_eq!(sum, 1.0);
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn builder_test() {
+    use std::rc::Rc;
+    use type_vec::VectBuilder;
+
+    fn set_first<T>(builder: VectBuilder<T, U3>, elem: T) -> VectBuilder<T, U3, U2> {
+        builder.push(elem)
+    }
+
+    let builder = set_first(VectBuilder::new(), 1);
+    assert_eq!(builder.len(), 1);
+    assert_eq!(builder.remaining(), 2);
+    let builder = builder.push(2);
+    assert_eq!(builder.as_slice(), [1, 2]);
+    assert_eq!(
+        format!("{:?}", builder),
+        "VectBuilder { elements: [1, 2], remaining: 1 }"
+    );
+
+    let vec: Vect<usize, U3> = builder.push(3).finish();
+    assert_eq!(vec, [1, 2, 3]);
+
+    let vec: Vect<usize, U0> = VectBuilder::new().finish();
+    assert!(vec.is_empty());
+
+    // abandoned builders drop the elements set so far
+    let elem = Rc::new(());
+    let builder = VectBuilder::<_, U4>::new()
+        .push(elem.clone())
+        .push(elem.clone());
+    assert_eq!(Rc::strong_count(&elem), 3);
+    drop(builder);
+    assert_eq!(Rc::strong_count(&elem), 1);
+
+    let vec = VectBuilder::<_, U2>::new()
+        .push(elem.clone())
+        .push(elem.clone())
+        .finish();
+    assert_eq!(Rc::strong_count(&elem), 3);
+    drop(vec);
+    assert_eq!(Rc::strong_count(&elem), 1);
+}
+
+#[test]
+fn constructor_test() {
+    use std::collections::VecDeque;
+
+    // static lengths are built directly
+    let vec = Vect::<usize, U4>::from_fn(|index| index * 2);
+    assert_eq!(vec, [0, 2, 4, 6]);
+    let vec = Vect::<String, U3>::from_elem("a".to_string());
+    assert_eq!(vec, ["a", "a", "a"]);
+    let vec = Vect::<usize, U3, VecDeque<usize>>::from_fn(|index| index);
+    assert_eq!(vec, [0, 1, 2]);
+    let vec: Vect<usize, U0> = Vect::from_fn(|_| unreachable!());
+    assert!(vec.is_empty());
+
+    // default elements
+    let vec = Vect::<usize, U3>::default();
+    assert_eq!(vec, [0, 0, 0]);
+    let vec = Vect::<String, U2, Vec<String>>::default();
+    assert_eq!(vec, ["", ""]);
+
+    // exact iterators
+    let vec = Vect::<usize, U3>::from_iter_exact(1..4).unwrap();
+    assert_eq!(vec, [1, 2, 3]);
+    let err = Vect::<usize, U3>::from_iter_exact(vec![1, 2]).unwrap_err();
+    assert_eq!((err.expected, err.actual), (3, 2));
+    assert_eq!(err.into_data().collect::<Vec<_>>(), [1, 2]);
+
+    // the reported length of the iterator is not trusted
+    #[derive(Debug)]
+    struct Lying<I>(I, usize);
+
+    impl<I: Iterator> Iterator for Lying<I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.1, Some(self.1))
+        }
+    }
+
+    impl<I: Iterator> ExactSizeIterator for Lying<I> {}
+
+    let err = Vect::<u64, U8, Vec<u64>>::from_iter_exact(Lying(0..1, 8)).unwrap_err();
+    assert_eq!((err.expected, err.actual), (8, 1));
+    assert_eq!(err.into_data().collect::<Vec<_>>(), [0]);
+    let err = Vect::<u64, U2, Vec<u64>>::from_iter_exact(Lying(0..4, 2)).unwrap_err();
+    assert_eq!((err.expected, err.actual), (2, 3));
+    assert_eq!(err.into_data().collect::<Vec<_>>(), [0, 1, 2, 3]);
+    let vec = Vect::<u64, U2, Vec<u64>>::from_iter_exact(Lying(0..2, 2)).unwrap();
+    assert_eq!(vec, [0, 1]);
+    // the inline storage panics on the missing items
+    let result =
+        std::panic::catch_unwind(|| Vect::<u64, U8>::from_iter_exact(Lying(0..1, 8)).is_ok());
+    assert!(result.is_err());
+
+    // dynamic length
+    let vec = Vect::<usize, Dyn>::repeat(7, 3);
+    assert_eq!(vec.len(), 3);
+    assert_eq!(vec, [7, 7, 7]);
+    assert!(Vect::<usize, Dyn>::repeat(7, 0).is_empty());
+}
+
+#[test]
+fn split_at_test() {
+    let vec = Vect::<usize, U5>::from_fn(|index| index);
+    let (left, right) = vec.split_at(U2::new());
+    let _: (&Vect<usize, U2>, &Vect<usize, U3>) = (&left, &right);
+    assert_eq!(left, [0, 1]);
+    assert_eq!(right, [2, 3, 4]);
+
+    let (left, right) = right.split_at(U3::new());
+    assert_eq!(left, [2, 3, 4]);
+    assert!(right.is_empty());
+
+    let vec = Vect::<String, U2, Vec<String>>::from_fn(|index| index.to_string());
+    let (left, right) = vec.split_at(U1::new());
+    assert_eq!(left, ["0"]);
+    assert_eq!(right, ["1"]);
+}
+
+#[test]
+fn secret_test() {
+    use std::mem::ManuallyDrop;
+    use type_vec::SecretVect;
+
+    let key = SecretVect::new(Vect::<u8, U4>::from_fn(|index| index as u8 + 1));
+    assert_eq!(key.len(), 4);
+    assert_eq!(key.get(U0::new()), &1);
+    assert_eq!(key.get(7), None);
+    assert_eq!(format!("{:?}", key), "SecretVect { len: 4, .. }");
+
+    // comparison
+    assert_eq!(key, key.clone());
+    assert_ne!(key, SecretVect::new(Vect::from([1u8, 2, 3, 5])));
+    assert_ne!(key, SecretVect::new(Vect::from([1u8, 2, 3])));
+    assert_eq!(
+        key,
+        SecretVect::new(Vect::<u8, Dyn>::from_vec(vec![1, 2, 3, 4]))
+    );
+
+    // typed operations
+    let (nonce, rest) = key.split_at(U3::new());
+    let _: (&SecretVect<u8, U3>, &SecretVect<u8, U1>) = (&nonce, &rest);
+    assert_eq!(nonce.expose_secret(), &[1, 2, 3]);
+    let rest = rest.push(9);
+    assert_eq!(rest.expose_secret(), &[4, 9]);
+
+    // heap storage is wiped before reallocation
+    let mut secret = SecretVect::new(Vect::<u32, Dyn>::new());
+    for index in 0..10 {
+        secret = secret.push(index);
+    }
+    secret.expose_secret_mut()[0] = 7;
+    assert_eq!(secret.get(0), Some(&7));
+    assert_eq!(secret.len(), 10);
+
+    // elements are wiped on drop
+    let mut secret = ManuallyDrop::new(SecretVect::new(Vect::<u64, U3>::from_elem(u64::MAX)));
+    unsafe { ManuallyDrop::drop(&mut secret) };
+    assert_eq!(secret.expose_secret(), &[0, 0, 0]);
+}
+
+#[test]
+fn ffi_test() {
+    use std::convert::TryFrom;
+    use type_vec::CVect;
+
+    extern "C" fn reverse(data: *mut [u8; 4]) {
+        unsafe { (*data).reverse() }
+    }
+
+    extern "C" fn sum(view: CVect<'_, u32>) -> u32 {
+        view.as_slice().iter().sum()
+    }
+
+    // the returned view borrows from the input view
+    extern "C" fn window<'a>(data: CVect<'a, u32>, begin: usize) -> CVect<'a, u32> {
+        let ptr = unsafe { data.as_ptr().add(begin) };
+        unsafe { CVect::from_raw_parts(ptr, data.len() - begin) }
+    }
+
+    // array pointers
+    let mut vec = Vect::<u8, U4>::from([1, 2, 3, 4]);
+    reverse(vec.as_mut_array_ptr());
+    assert_eq!(vec, [4, 3, 2, 1]);
+    assert_eq!(unsafe { *vec.as_array_ptr() }, [4, 3, 2, 1]);
+    vec.as_mut_array::<4>()[0] = 5;
+    assert_eq!(vec.as_array(), &[5, 3, 2, 1]);
+
+    // views
+    let vec = Vect::<u32, Dyn>::from_vec(vec![1, 2, 3, 4, 5]);
+    assert_eq!(sum(vec.as_c_vect()), 15);
+    assert_eq!(sum(CVect::from(&[] as &[u32])), 0);
+    assert_eq!(
+        sum(unsafe { CVect::from_raw_parts(std::ptr::null(), 0) }),
+        0
+    );
+
+    // checked imports
+    let view = window(vec.as_c_vect(), 2);
+    assert_eq!(format!("{:?}", view), "[3, 4, 5]");
+    let tail = Vect::<u32, U3>::try_from(view).unwrap();
+    assert_eq!(tail, [3, 4, 5]);
+    let err = Vect::<u32, U2>::try_from(view).unwrap_err();
+    assert_eq!((err.expected, err.actual), (2, 3));
+    let tail = view.try_as_vect::<U3>().unwrap();
+    assert_eq!(tail.get(U2::new()), &5);
+    assert!(view.try_as_vect::<U4>().is_err());
+    let empty = unsafe { CVect::<u32>::from_raw_parts(std::ptr::null(), 0) };
+    assert_eq!(empty.try_as_vect::<U0>().unwrap().len(), 0);
+}